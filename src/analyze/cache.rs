@@ -3,6 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
@@ -11,9 +17,35 @@ use std::time::SystemTime;
 use super::lock_or_recover;
 use super::types::{AnalysisMode, AnalysisResult};
 
+/// Bumped whenever a grammar or query change would make previously cached results stale
+const GRAMMAR_VERSION: u32 = 1;
+
+/// Total size an on-disk cache directory is allowed to grow to before the oldest entries
+/// (by file modification time) are evicted
+const DEFAULT_DISK_BUDGET_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Magic header identifying an [`MtimeCache`] file, checked before the schema-version byte
+const MTIME_CACHE_MAGIC: &[u8; 4] = b"ACMC";
+
+/// Bumped whenever the [`MtimeCache`] binary layout changes, so a file written by an older
+/// release is discarded instead of mis-parsed
+const MTIME_CACHE_VERSION: u8 = 1;
+
+/// Fixed file name for the single-file mtime cache, created under the directory passed to
+/// [`AnalysisCache::with_mtime_disk`]
+const MTIME_CACHE_FILE_NAME: &str = "mtime-cache.bin";
+
+/// In-memory LRU keyed on content hash rather than `SystemTime`, with an optional
+/// write-through tier on disk so results survive across process runs.
+///
+/// Keying on content (instead of mtime, which is unreliable across checkouts and clones)
+/// means a file is only ever re-parsed when its bytes, [`AnalysisMode`], or
+/// `ast_recursion_limit` actually change.
 #[derive(Clone)]
 pub struct AnalysisCache {
-    cache: Arc<Mutex<LruCache<CacheKey, Arc<AnalysisResult>>>>,
+    memory: Arc<Mutex<LruCache<CacheKey, Arc<AnalysisResult>>>>,
+    disk: Option<ContentCache>,
+    mtime: Option<MtimeCache>,
     #[allow(dead_code)]
     max_size: usize,
 }
@@ -21,12 +53,46 @@ pub struct AnalysisCache {
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
 struct CacheKey {
     path: PathBuf,
-    modified: SystemTime,
+    content_hash: u64,
     mode: AnalysisMode,
+    ast_recursion_limit: Option<usize>,
 }
 
 impl AnalysisCache {
     pub fn new(max_size: usize) -> Self {
+        Self {
+            memory: Arc::new(Mutex::new(Self::make_lru(max_size))),
+            disk: None,
+            mtime: None,
+            max_size,
+        }
+    }
+
+    /// Same as [`Self::new`], but also persists entries to a content-hashed cache directory
+    /// so they survive across process runs, bounded to [`DEFAULT_DISK_BUDGET_BYTES`] total.
+    pub fn with_disk(dir: PathBuf, max_size: usize) -> Self {
+        Self {
+            memory: Arc::new(Mutex::new(Self::make_lru(max_size))),
+            disk: Some(ContentCache::new(dir)),
+            mtime: None,
+            max_size,
+        }
+    }
+
+    /// Same as [`Self::new`], but persists entries to a single versioned binary file under
+    /// `dir`, keyed by absolute path and modification time instead of content hash. Cheaper
+    /// than [`Self::with_disk`] because a hit never requires reading or hashing the file's
+    /// contents, at the cost of treating a touched-but-unchanged file as a miss.
+    pub fn with_mtime_disk(dir: PathBuf, max_size: usize) -> Self {
+        Self {
+            memory: Arc::new(Mutex::new(Self::make_lru(max_size))),
+            disk: None,
+            mtime: Some(MtimeCache::load(dir.join(MTIME_CACHE_FILE_NAME))),
+            max_size,
+        }
+    }
+
+    fn make_lru(max_size: usize) -> LruCache<CacheKey, Arc<AnalysisResult>> {
         let size = NonZeroUsize::new(max_size).unwrap_or_else(|| {
             eprintln!(
                 "Warning: Invalid cache size {}, using default 100",
@@ -34,44 +100,77 @@ impl AnalysisCache {
             );
             NonZeroUsize::new(100).unwrap()
         });
+        LruCache::new(size)
+    }
 
-        Self {
-            cache: Arc::new(Mutex::new(LruCache::new(size))),
-            max_size,
-        }
+    /// Fold `mode` into the content hash handed to the [`ContentCache`] tier, whose entries
+    /// don't record a mode of their own, so a result extracted under one mode never answers
+    /// a lookup for another
+    fn disk_hash(content_hash: u64, mode: &AnalysisMode) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content_hash.hash(&mut hasher);
+        mode.hash(&mut hasher);
+        hasher.finish()
     }
 
     pub fn get(
         &self,
         path: &Path,
-        modified: SystemTime,
+        content_hash: u64,
         mode: &AnalysisMode,
+        ast_recursion_limit: Option<usize>,
     ) -> Option<AnalysisResult> {
-        let mut cache = lock_or_recover(&self.cache, |c| c.clear());
         let key = CacheKey {
             path: path.to_path_buf(),
-            modified,
+            content_hash,
             mode: *mode,
+            ast_recursion_limit,
         };
 
-        cache.get(&key).map(|result| (**result).clone())
+        {
+            let mut cache = lock_or_recover(&self.memory, |c| c.clear());
+            if let Some(result) = cache.get(&key) {
+                return Some((**result).clone());
+            }
+        }
+
+        let result = if let Some(disk) = &self.disk {
+            disk.get(path, Self::disk_hash(content_hash, mode), ast_recursion_limit)?
+        } else {
+            self.mtime.as_ref()?.get(path, mode, ast_recursion_limit)?
+        };
+
+        let mut cache = lock_or_recover(&self.memory, |c| c.clear());
+        cache.put(key, Arc::new(result.clone()));
+        Some(result)
     }
 
     pub fn put(
         &self,
         path: PathBuf,
-        modified: SystemTime,
+        content_hash: u64,
         mode: &AnalysisMode,
+        ast_recursion_limit: Option<usize>,
         result: AnalysisResult,
     ) {
-        let mut cache = lock_or_recover(&self.cache, |c| c.clear());
         let key = CacheKey {
-            path,
-            modified,
+            path: path.clone(),
+            content_hash,
             mode: *mode,
+            ast_recursion_limit,
         };
 
-        cache.put(key, Arc::new(result));
+        {
+            let mut cache = lock_or_recover(&self.memory, |c| c.clear());
+            cache.put(key, Arc::new(result.clone()));
+        }
+
+        if let Some(disk) = &self.disk {
+            disk.put(&path, Self::disk_hash(content_hash, mode), ast_recursion_limit, &result);
+        }
+        if let Some(mtime) = &self.mtime {
+            mtime.put(&path, mode, ast_recursion_limit, &result);
+        }
     }
 }
 
@@ -81,10 +180,293 @@ impl Default for AnalysisCache {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct ContentCacheEntry {
+    content_hash: u64,
+    grammar_version: u32,
+    ast_recursion_limit: Option<usize>,
+    result: AnalysisResult,
+}
+
+/// Content-hashed, on-disk cache for [`AnalysisResult`]s, keyed by absolute file path.
+///
+/// Unlike an in-memory LRU, entries here persist across process runs: a file is re-parsed
+/// only when its content hash, the grammar version, or the `--ast-recursion-limit` used to
+/// extract it no longer matches the stored entry, so results survive checkouts that touch
+/// mtimes without changing bytes. The directory is kept under [`DEFAULT_DISK_BUDGET_BYTES`]
+/// by evicting the oldest entries first.
+#[derive(Clone)]
+pub struct ContentCache {
+    dir: PathBuf,
+}
+
+impl ContentCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Hash a file's content for cache-key purposes (not cryptographic)
+    pub fn hash_content(content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    pub fn get(
+        &self,
+        path: &Path,
+        content_hash: u64,
+        ast_recursion_limit: Option<usize>,
+    ) -> Option<AnalysisResult> {
+        let data = fs::read(self.entry_path(path)).ok()?;
+        let entry: ContentCacheEntry = serde_json::from_slice(&data).ok()?;
+
+        if entry.content_hash == content_hash
+            && entry.grammar_version == GRAMMAR_VERSION
+            && entry.ast_recursion_limit == ast_recursion_limit
+        {
+            Some(entry.result)
+        } else {
+            None
+        }
+    }
+
+    pub fn put(
+        &self,
+        path: &Path,
+        content_hash: u64,
+        ast_recursion_limit: Option<usize>,
+        result: &AnalysisResult,
+    ) {
+        let entry = ContentCacheEntry {
+            content_hash,
+            grammar_version: GRAMMAR_VERSION,
+            ast_recursion_limit,
+            result: result.clone(),
+        };
+
+        if fs::create_dir_all(&self.dir).is_ok()
+            && let Ok(data) = serde_json::to_vec(&entry)
+        {
+            let _ = fs::write(self.entry_path(path), data);
+            Self::evict_if_over_budget(&self.dir, DEFAULT_DISK_BUDGET_BYTES);
+        }
+    }
+
+    /// Remove the oldest entries (by file modification time) until the cache directory's
+    /// total size is back under `budget` bytes
+    fn evict_if_over_budget(dir: &Path, budget: u64) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                Some((e.path(), meta.len(), meta.modified().ok()?))
+            })
+            .collect();
+
+        let mut total: u64 = files.iter().map(|(_, size, _)| *size).sum();
+        if total <= budget {
+            return;
+        }
+
+        files.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in files {
+            if total <= budget {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+            }
+        }
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Clone)]
+struct MtimeKey {
+    path: PathBuf,
+    mode: AnalysisMode,
+    ast_recursion_limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct MtimeCacheEntry {
+    path: PathBuf,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    mode: AnalysisMode,
+    ast_recursion_limit: Option<usize>,
+    result: AnalysisResult,
+}
+
+/// Path- and modification-time-keyed on-disk cache for [`AnalysisResult`]s, persisted as a
+/// single versioned binary file instead of one file per entry (see [`ContentCache`]).
+///
+/// A hit only needs `metadata().modified()`, never the file's bytes, so it's cheaper than
+/// [`ContentCache`] at the cost of a false miss when a file's mtime changes without its
+/// content changing (e.g. a `touch`, or a checkout that doesn't preserve mtimes). The file
+/// starts with a magic header and a schema-version byte so a cache written by an older,
+/// incompatible release is discarded rather than mis-parsed, and entries are length-prefixed
+/// so a partially written file (e.g. a crash mid-save) truncates cleanly to an empty cache
+/// instead of failing to load.
+#[derive(Clone)]
+pub struct MtimeCache {
+    file_path: PathBuf,
+    entries: Arc<Mutex<HashMap<MtimeKey, MtimeCacheEntry>>>,
+}
+
+impl MtimeCache {
+    /// Load entries from `file_path`, treating a missing file, a bad magic header, a
+    /// mismatched schema version, or any truncated/corrupt entry as an empty cache.
+    pub fn load(file_path: PathBuf) -> Self {
+        let entries = Self::read_entries(&file_path).unwrap_or_default();
+        Self {
+            file_path,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    fn read_entries(file_path: &Path) -> Option<HashMap<MtimeKey, MtimeCacheEntry>> {
+        let mut data = Vec::new();
+        fs::File::open(file_path).ok()?.read_to_end(&mut data).ok()?;
+
+        if data.len() < 5 || &data[0..4] != MTIME_CACHE_MAGIC || data[4] != MTIME_CACHE_VERSION {
+            return None;
+        }
+
+        let mut entries = HashMap::new();
+        let mut offset = 5;
+        while offset + 4 <= data.len() {
+            let len = u32::from_le_bytes(data[offset..offset + 4].try_into().ok()?) as usize;
+            offset += 4;
+            if offset + len > data.len() {
+                // Partial/truncated entry (e.g. a crash mid-write): discard the whole file
+                // as an empty cache rather than erroring out.
+                return None;
+            }
+            let Ok(entry) = serde_json::from_slice::<MtimeCacheEntry>(&data[offset..offset + len])
+            else {
+                return None;
+            };
+            offset += len;
+
+            let key = MtimeKey {
+                path: entry.path.clone(),
+                mode: entry.mode,
+                ast_recursion_limit: entry.ast_recursion_limit,
+            };
+            entries.insert(key, entry);
+        }
+
+        // Trailing bytes too short for a length prefix are a truncated entry as well
+        if offset != data.len() {
+            return None;
+        }
+
+        Some(entries)
+    }
+
+    pub fn get(
+        &self,
+        path: &Path,
+        mode: &AnalysisMode,
+        ast_recursion_limit: Option<usize>,
+    ) -> Option<AnalysisResult> {
+        let modified = fs::metadata(path).ok()?.modified().ok()?;
+        let (mtime_secs, mtime_nanos) = to_epoch_parts(modified);
+
+        let key = MtimeKey {
+            path: path.to_path_buf(),
+            mode: *mode,
+            ast_recursion_limit,
+        };
+
+        let entries = lock_or_recover(&self.entries, |e| e.clear());
+        let entry = entries.get(&key)?;
+        if entry.mtime_secs == mtime_secs && entry.mtime_nanos == mtime_nanos {
+            Some(entry.result.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn put(
+        &self,
+        path: &Path,
+        mode: &AnalysisMode,
+        ast_recursion_limit: Option<usize>,
+        result: &AnalysisResult,
+    ) {
+        let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) else {
+            return;
+        };
+        let (mtime_secs, mtime_nanos) = to_epoch_parts(modified);
+
+        let key = MtimeKey {
+            path: path.to_path_buf(),
+            mode: *mode,
+            ast_recursion_limit,
+        };
+        let entry = MtimeCacheEntry {
+            path: path.to_path_buf(),
+            mtime_secs,
+            mtime_nanos,
+            mode: *mode,
+            ast_recursion_limit,
+            result: result.clone(),
+        };
+
+        let mut entries = lock_or_recover(&self.entries, |e| e.clear());
+        entries.insert(key, entry);
+        Self::persist(&self.file_path, &entries);
+    }
+
+    fn persist(file_path: &Path, entries: &HashMap<MtimeKey, MtimeCacheEntry>) {
+        let Some(parent) = file_path.parent() else {
+            return;
+        };
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(MTIME_CACHE_MAGIC);
+        data.push(MTIME_CACHE_VERSION);
+        for entry in entries.values() {
+            let Ok(encoded) = serde_json::to_vec(entry) else {
+                continue;
+            };
+            data.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+            data.extend_from_slice(&encoded);
+        }
+
+        if let Ok(mut file) = fs::File::create(file_path) {
+            let _ = file.write_all(&data);
+        }
+    }
+}
+
+/// Split a [`SystemTime`] into whole seconds and nanoseconds since `UNIX_EPOCH`, clamping to
+/// zero if the system clock is set before the epoch (there's no meaningful mtime to key on)
+fn to_epoch_parts(time: SystemTime) -> (u64, u32) {
+    let duration = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    (duration.as_secs(), duration.subsec_nanos())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::SystemTime;
 
     fn sample_result() -> AnalysisResult {
         AnalysisResult::empty(10)
@@ -94,25 +476,29 @@ mod tests {
     fn cache_put_and_get() {
         let cache = AnalysisCache::new(10);
         let path = PathBuf::from("/tmp/test.rs");
-        let modified = SystemTime::now();
+        let hash = ContentCache::hash_content("fn main() {}");
         let mode = AnalysisMode::Semantic;
 
-        cache.put(path.clone(), modified, &mode, sample_result());
-        let result = cache.get(&path, modified, &mode);
+        cache.put(path.clone(), hash, &mode, None, sample_result());
+        let result = cache.get(&path, hash, &mode, None);
         assert!(result.is_some());
         assert_eq!(result.unwrap().line_count, 10);
     }
 
     #[test]
-    fn cache_miss_on_different_time() {
+    fn cache_miss_on_different_content_hash() {
         let cache = AnalysisCache::new(10);
         let path = PathBuf::from("/tmp/test.rs");
-        let t1 = SystemTime::UNIX_EPOCH;
-        let t2 = SystemTime::now();
         let mode = AnalysisMode::Semantic;
 
-        cache.put(path.clone(), t1, &mode, sample_result());
-        let result = cache.get(&path, t2, &mode);
+        cache.put(
+            path.clone(),
+            ContentCache::hash_content("fn a() {}"),
+            &mode,
+            None,
+            sample_result(),
+        );
+        let result = cache.get(&path, ContentCache::hash_content("fn b() {}"), &mode, None);
         assert!(result.is_none());
     }
 
@@ -120,58 +506,277 @@ mod tests {
     fn cache_miss_on_different_mode() {
         let cache = AnalysisCache::new(10);
         let path = PathBuf::from("/tmp/test.rs");
-        let modified = SystemTime::now();
+        let hash = ContentCache::hash_content("fn main() {}");
 
         cache.put(
             path.clone(),
-            modified,
+            hash,
             &AnalysisMode::Semantic,
+            None,
             sample_result(),
         );
-        let result = cache.get(&path, modified, &AnalysisMode::Structure);
+        let result = cache.get(&path, hash, &AnalysisMode::Structure, None);
         assert!(result.is_none());
     }
 
     #[test]
     fn cache_miss_on_different_path() {
+        let hash = ContentCache::hash_content("fn main() {}");
         let cache = AnalysisCache::new(10);
-        let modified = SystemTime::now();
         let mode = AnalysisMode::Semantic;
 
-        cache.put(PathBuf::from("/a.rs"), modified, &mode, sample_result());
-        let result = cache.get(Path::new("/b.rs"), modified, &mode);
+        cache.put(PathBuf::from("/a.rs"), hash, &mode, None, sample_result());
+        let result = cache.get(Path::new("/b.rs"), hash, &mode, None);
         assert!(result.is_none());
     }
 
+    #[test]
+    fn cache_miss_on_different_recursion_limit() {
+        let cache = AnalysisCache::new(10);
+        let path = PathBuf::from("/tmp/test.rs");
+        let hash = ContentCache::hash_content("fn main() {}");
+        let mode = AnalysisMode::Semantic;
+
+        cache.put(path.clone(), hash, &mode, Some(50), sample_result());
+        assert!(cache.get(&path, hash, &mode, Some(100)).is_none());
+        assert!(cache.get(&path, hash, &mode, Some(50)).is_some());
+    }
+
     #[test]
     fn cache_evicts_when_full() {
         let cache = AnalysisCache::new(2);
-        let t = SystemTime::now();
         let mode = AnalysisMode::Semantic;
+        let hash_a = ContentCache::hash_content("a");
+        let hash_b = ContentCache::hash_content("b");
+        let hash_c = ContentCache::hash_content("c");
 
-        cache.put(PathBuf::from("/a.rs"), t, &mode, sample_result());
-        cache.put(PathBuf::from("/b.rs"), t, &mode, sample_result());
-        cache.put(PathBuf::from("/c.rs"), t, &mode, sample_result());
+        cache.put(PathBuf::from("/a.rs"), hash_a, &mode, None, sample_result());
+        cache.put(PathBuf::from("/b.rs"), hash_b, &mode, None, sample_result());
+        cache.put(PathBuf::from("/c.rs"), hash_c, &mode, None, sample_result());
 
         // /a.rs should have been evicted (LRU)
-        assert!(cache.get(Path::new("/a.rs"), t, &mode).is_none());
-        assert!(cache.get(Path::new("/c.rs"), t, &mode).is_some());
+        assert!(cache.get(Path::new("/a.rs"), hash_a, &mode, None).is_none());
+        assert!(cache.get(Path::new("/c.rs"), hash_c, &mode, None).is_some());
     }
 
     #[test]
     fn cache_default_works() {
         let cache = AnalysisCache::default();
-        let t = SystemTime::now();
+        let hash = ContentCache::hash_content("fn x() {}");
         cache.put(
             PathBuf::from("/x.rs"),
-            t,
+            hash,
             &AnalysisMode::Semantic,
+            None,
             sample_result(),
         );
         assert!(
             cache
-                .get(Path::new("/x.rs"), t, &AnalysisMode::Semantic)
+                .get(Path::new("/x.rs"), hash, &AnalysisMode::Semantic, None)
                 .is_some()
         );
     }
+
+    #[test]
+    fn with_disk_falls_back_to_disk_tier_on_memory_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AnalysisCache::with_disk(dir.path().to_path_buf(), 10);
+        let path = PathBuf::from("/tmp/test.rs");
+        let hash = ContentCache::hash_content("fn main() {}");
+        let mode = AnalysisMode::Semantic;
+
+        cache.put(path.clone(), hash, &mode, None, sample_result());
+
+        // A fresh cache (simulating a new process) only has the disk tier populated.
+        let reopened = AnalysisCache::with_disk(dir.path().to_path_buf(), 10);
+        let result = reopened.get(&path, hash, &mode, None);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().line_count, 10);
+    }
+
+    #[test]
+    fn content_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContentCache::new(dir.path().to_path_buf());
+        let path = Path::new("/tmp/foo.rs");
+        let hash = ContentCache::hash_content("fn main() {}");
+
+        cache.put(path, hash, None, &sample_result());
+        let result = cache.get(path, hash, None);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().line_count, 10);
+    }
+
+    #[test]
+    fn content_cache_miss_on_content_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContentCache::new(dir.path().to_path_buf());
+        let path = Path::new("/tmp/foo.rs");
+
+        cache.put(
+            path,
+            ContentCache::hash_content("fn a() {}"),
+            None,
+            &sample_result(),
+        );
+        let result = cache.get(path, ContentCache::hash_content("fn b() {}"), None);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn content_cache_miss_on_recursion_limit_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContentCache::new(dir.path().to_path_buf());
+        let path = Path::new("/tmp/foo.rs");
+        let hash = ContentCache::hash_content("fn main() {}");
+
+        cache.put(path, hash, Some(50), &sample_result());
+        assert!(cache.get(path, hash, Some(100)).is_none());
+        assert!(cache.get(path, hash, Some(50)).is_some());
+    }
+
+    #[test]
+    fn content_cache_miss_on_missing_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContentCache::new(dir.path().to_path_buf());
+        assert!(cache.get(Path::new("/tmp/nope.rs"), 0, None).is_none());
+    }
+
+    #[test]
+    fn content_cache_evicts_oldest_when_over_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContentCache::new(dir.path().to_path_buf());
+
+        for i in 0..3 {
+            cache.put(
+                Path::new(&format!("/tmp/file{}.rs", i)),
+                ContentCache::hash_content(&format!("fn f{}() {{}}", i)),
+                None,
+                &sample_result(),
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let entry_size = fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .metadata()
+            .unwrap()
+            .len();
+
+        ContentCache::evict_if_over_budget(dir.path(), entry_size + 1);
+
+        let remaining = fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(
+            remaining, 1,
+            "expected only the newest entry to survive eviction"
+        );
+    }
+
+    #[test]
+    fn mtime_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("foo.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let cache = MtimeCache::load(dir.path().join(MTIME_CACHE_FILE_NAME));
+        let mode = AnalysisMode::Semantic;
+        cache.put(&file, &mode, None, &sample_result());
+
+        // A fresh cache (simulating a new process) only has what was persisted to disk.
+        let reopened = MtimeCache::load(dir.path().join(MTIME_CACHE_FILE_NAME));
+        let result = reopened.get(&file, &mode, None);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().line_count, 10);
+    }
+
+    #[test]
+    fn mtime_cache_miss_on_mtime_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("foo.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let cache = MtimeCache::load(dir.path().join(MTIME_CACHE_FILE_NAME));
+        let mode = AnalysisMode::Semantic;
+        cache.put(&file, &mode, None, &sample_result());
+
+        // Bump the mtime without touching the cache entry, simulating a checkout or `touch`.
+        let newer = SystemTime::now() + std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(newer)
+            .unwrap();
+
+        assert!(cache.get(&file, &mode, None).is_none());
+    }
+
+    #[test]
+    fn mtime_cache_miss_on_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MtimeCache::load(dir.path().join(MTIME_CACHE_FILE_NAME));
+        let mode = AnalysisMode::Semantic;
+        assert!(cache.get(Path::new("/tmp/does-not-exist.rs"), &mode, None).is_none());
+    }
+
+    #[test]
+    fn mtime_cache_rejects_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("foo.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let cache_path = dir.path().join(MTIME_CACHE_FILE_NAME);
+        let cache = MtimeCache::load(cache_path.clone());
+        cache.put(&file, &AnalysisMode::Semantic, None, &sample_result());
+
+        // Overwrite the schema-version byte as if the file was written by an older release.
+        let mut data = fs::read(&cache_path).unwrap();
+        data[4] = MTIME_CACHE_VERSION.wrapping_add(1);
+        fs::write(&cache_path, data).unwrap();
+
+        let reloaded = MtimeCache::load(cache_path);
+        assert!(
+            reloaded.get(&file, &AnalysisMode::Semantic, None).is_none(),
+            "a version-mismatched cache file should load as empty"
+        );
+    }
+
+    #[test]
+    fn mtime_cache_truncated_file_loads_as_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("foo.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let cache_path = dir.path().join(MTIME_CACHE_FILE_NAME);
+        let cache = MtimeCache::load(cache_path.clone());
+        cache.put(&file, &AnalysisMode::Semantic, None, &sample_result());
+
+        // Truncate the file as if the process crashed mid-write.
+        let mut data = fs::read(&cache_path).unwrap();
+        data.truncate(data.len() - 3);
+        fs::write(&cache_path, data).unwrap();
+
+        let reloaded = MtimeCache::load(cache_path);
+        assert!(reloaded.get(&file, &AnalysisMode::Semantic, None).is_none());
+    }
+
+    #[test]
+    fn with_mtime_disk_falls_back_to_disk_tier_on_memory_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("foo.rs");
+        fs::write(&file, "fn main() {}").unwrap();
+
+        let cache = AnalysisCache::with_mtime_disk(dir.path().to_path_buf(), 10);
+        let hash = ContentCache::hash_content("fn main() {}");
+        let mode = AnalysisMode::Semantic;
+        cache.put(file.clone(), hash, &mode, None, sample_result());
+
+        let reopened = AnalysisCache::with_mtime_disk(dir.path().to_path_buf(), 10);
+        let result = reopened.get(&file, hash, &mode, None);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().line_count, 10);
+    }
 }
@@ -0,0 +1,345 @@
+// Copyright 2024 Block, Inc. (original code from https://github.com/block/goose)
+// Copyright 2025 utapyngo (modifications)
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file name discovered by walking up from the analyzed path
+pub const CONFIG_FILE_NAME: &str = ".code-analyzer.toml";
+
+/// Defaults for `analyze`'s traversal/analysis parameters, loaded from a project config file
+/// so callers don't have to pass everything through the long argument list.
+///
+/// Composed the way Mercurial layers `hgrc` files: a config may pull in other config files via
+/// a top-level `include = [...]`, each merged (recursively) in list order before the including
+/// file's own keys are applied on top, so the including file always wins ties. Example:
+///
+/// ```toml
+/// include = ["../base.code-analyzer.toml"]
+///
+/// max_depth = 4
+/// follow_depth = 2
+/// ast_recursion_limit = 500
+///
+/// [files]
+/// include = ["src/**/*.rs"]
+/// exclude = ["**/*_test.rs"]
+///
+/// [languages]
+/// go = false
+/// ```
+///
+/// ```toml
+/// mtime_cache = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnalysisConfig {
+    pub max_depth: Option<u32>,
+    pub follow_depth: Option<u32>,
+    pub ast_recursion_limit: Option<usize>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub languages: HashMap<String, bool>,
+    /// Use the mtime-keyed on-disk cache ([`crate::analyze::cache::MtimeCache`]) instead of the
+    /// default content-hashed one
+    pub mtime_cache: Option<bool>,
+}
+
+impl AnalysisConfig {
+    /// Walk up from `start` (or its parent, if `start` is a file) looking for
+    /// [`CONFIG_FILE_NAME`], returning the first ancestor directory that has one
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        let mut dir = if start.is_dir() { Some(start) } else { start.parent() };
+        while let Some(d) = dir {
+            let candidate = d.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            dir = d.parent();
+        }
+        None
+    }
+
+    /// Load `path`, recursively merging any `include = [...]` layers (relative to `path`'s own
+    /// directory) in list order, before `path`'s own keys are applied on top. Fails if `path`
+    /// can't be read/resolved, or if an include forms a cycle.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut stack = HashSet::new();
+        Self::load_layer(path, &mut stack)
+    }
+
+    fn load_layer(path: &Path, stack: &mut HashSet<PathBuf>) -> Result<Self, String> {
+        let canonical = fs::canonicalize(path)
+            .map_err(|e| format!("cannot resolve config {}: {}", path.display(), e))?;
+        if !stack.insert(canonical.clone()) {
+            return Err(format!("config include cycle detected at {}", path.display()));
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read config {}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut merged = Self::default();
+        for include in Self::string_array(&Self::top_level_body(&content), "include").unwrap_or_default() {
+            let included_path = dir.join(&include);
+            let layer = Self::load_layer(&included_path, stack)?;
+            merged.merge(layer);
+        }
+
+        merged.merge(Self::parse_own(&content));
+        stack.remove(&canonical);
+        Ok(merged)
+    }
+
+    fn parse_own(content: &str) -> Self {
+        let files = Self::section_body(content, "files");
+        Self {
+            max_depth: Self::top_level_value(content, "max_depth").and_then(|v| v.parse().ok()),
+            follow_depth: Self::top_level_value(content, "follow_depth")
+                .and_then(|v| v.parse().ok()),
+            ast_recursion_limit: Self::top_level_value(content, "ast_recursion_limit")
+                .and_then(|v| v.parse().ok()),
+            include: files
+                .and_then(|body| Self::string_array(body, "include"))
+                .unwrap_or_default(),
+            exclude: files
+                .and_then(|body| Self::string_array(body, "exclude"))
+                .unwrap_or_default(),
+            languages: Self::parse_language_toggles(content),
+            mtime_cache: Self::top_level_value(content, "mtime_cache").map(|v| v == "true"),
+        }
+    }
+
+    /// Later values win: unset fields in `other` leave `self` unchanged; anything `other` sets
+    /// replaces the prior value. Language toggles merge key-by-key rather than wholesale.
+    fn merge(&mut self, other: Self) {
+        if other.max_depth.is_some() {
+            self.max_depth = other.max_depth;
+        }
+        if other.follow_depth.is_some() {
+            self.follow_depth = other.follow_depth;
+        }
+        if other.ast_recursion_limit.is_some() {
+            self.ast_recursion_limit = other.ast_recursion_limit;
+        }
+        if !other.include.is_empty() {
+            self.include = other.include;
+        }
+        if !other.exclude.is_empty() {
+            self.exclude = other.exclude;
+        }
+        if other.mtime_cache.is_some() {
+            self.mtime_cache = other.mtime_cache;
+        }
+        self.languages.extend(other.languages);
+    }
+
+    /// Whether the `[languages]` table disables `language` (defaults to enabled when unset)
+    pub fn language_enabled(&self, language: &str) -> bool {
+        self.languages.get(language).copied().unwrap_or(true)
+    }
+
+    fn parse_language_toggles(content: &str) -> HashMap<String, bool> {
+        let Some(body) = Self::section_body(content, "languages") else {
+            return HashMap::new();
+        };
+
+        body.lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut parts = line.splitn(2, '=');
+                let (k, v) = (parts.next()?.trim(), parts.next()?.trim());
+                Some((k.to_string(), v == "true"))
+            })
+            .collect()
+    }
+
+    /// Find `key = value` among the top-level lines of a config file, i.e. before its first
+    /// `[section]` header
+    fn top_level_value(content: &str, key: &str) -> Option<String> {
+        let body = Self::top_level_body(content);
+        for line in body.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let (Some(k), Some(v)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if k.trim() == key {
+                return Some(v.trim().trim_matches('"').to_string());
+            }
+        }
+        None
+    }
+
+    /// The lines of a config file before its first (possibly indented) `[section]` header
+    fn top_level_body(content: &str) -> String {
+        content
+            .lines()
+            .take_while(|line| !line.trim_start().starts_with('['))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Parse a `key = ["a", "b"]` array from within `body`
+    fn string_array(body: &str, key: &str) -> Option<Vec<String>> {
+        for line in body.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let (Some(k), Some(v)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if k.trim() != key {
+                continue;
+            }
+            let v = v.trim();
+            let inner = v.strip_prefix('[')?.strip_suffix(']')?;
+            return Some(
+                inner
+                    .split(',')
+                    .map(|s| s.trim().trim_matches('"').to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            );
+        }
+        None
+    }
+
+    /// Extract the raw lines between a `[section]` header and the next `[...]` header
+    fn section_body<'a>(content: &'a str, section: &str) -> Option<&'a str> {
+        let header = format!("[{}]", section);
+        let start = content.find(&header)? + header.len();
+        let rest = &content[start..];
+        let end = rest
+            .match_indices('\n')
+            .map(|(i, _)| i + 1)
+            .find(|&i| rest[i..].trim_start().starts_with('['))
+            .unwrap_or(rest.len());
+        Some(&rest[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_scalars_and_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(
+            dir.path(),
+            ".code-analyzer.toml",
+            r#"
+                max_depth = 4
+                follow_depth = 1
+                ast_recursion_limit = 500
+
+                [files]
+                include = ["src/**/*.rs"]
+                exclude = ["**/*_test.rs"]
+
+                [languages]
+                go = false
+                rust = true
+            "#,
+        );
+
+        let config = AnalysisConfig::load(&path).unwrap();
+        assert_eq!(config.max_depth, Some(4));
+        assert_eq!(config.follow_depth, Some(1));
+        assert_eq!(config.ast_recursion_limit, Some(500));
+        assert_eq!(config.include, vec!["src/**/*.rs".to_string()]);
+        assert_eq!(config.exclude, vec!["**/*_test.rs".to_string()]);
+        assert!(!config.language_enabled("go"));
+        assert!(config.language_enabled("rust"));
+        assert!(config.language_enabled("python"));
+    }
+
+    #[test]
+    fn parses_mtime_cache_toggle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(dir.path(), ".code-analyzer.toml", "mtime_cache = true\n");
+
+        let config = AnalysisConfig::load(&path).unwrap();
+        assert_eq!(config.mtime_cache, Some(true));
+    }
+
+    #[test]
+    fn include_layers_merge_with_later_overriding_earlier() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "base.code-analyzer.toml",
+            "max_depth = 2\nfollow_depth = 1\n",
+        );
+        let path = write(
+            dir.path(),
+            ".code-analyzer.toml",
+            "include = [\"base.code-analyzer.toml\"]\nmax_depth = 5\n",
+        );
+
+        let config = AnalysisConfig::load(&path).unwrap();
+        // Overridden by the including file
+        assert_eq!(config.max_depth, Some(5));
+        // Inherited from the included base, untouched by the including file
+        assert_eq!(config.follow_depth, Some(1));
+    }
+
+    #[test]
+    fn relative_include_paths_resolve_against_including_files_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+        write(dir.path(), "base.code-analyzer.toml", "max_depth = 7\n");
+        let path = write(
+            &nested,
+            ".code-analyzer.toml",
+            "include = [\"../base.code-analyzer.toml\"]\n",
+        );
+
+        let config = AnalysisConfig::load(&path).unwrap();
+        assert_eq!(config.max_depth, Some(7));
+    }
+
+    #[test]
+    fn detects_include_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.code-analyzer.toml", "include = [\"b.code-analyzer.toml\"]\n");
+        let b = write(dir.path(), "b.code-analyzer.toml", "include = [\"a.code-analyzer.toml\"]\n");
+
+        let err = AnalysisConfig::load(&b).unwrap_err();
+        assert!(err.contains("cycle"), "expected a cycle error, got: {err}");
+    }
+
+    #[test]
+    fn discover_walks_up_to_the_nearest_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a/b/c");
+        fs::create_dir_all(&nested).unwrap();
+        let config_path = write(dir.path(), CONFIG_FILE_NAME, "max_depth = 1\n");
+
+        assert_eq!(AnalysisConfig::discover(&nested), Some(config_path));
+    }
+
+    #[test]
+    fn discover_returns_none_without_a_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(AnalysisConfig::discover(dir.path()), None);
+    }
+}
@@ -3,18 +3,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::types::{AnalysisResult, CallChain, ReferenceType};
 
 /// Sentinel value used to represent type references as callers in the call graph
 const REFERENCE_CALLER: &str = "<reference>";
 
+/// Outcome of resolving an unqualified callee name against a file's scope
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CalleeResolution {
+    Resolved(String),
+    Ambiguous(Vec<String>),
+    Unresolved,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CallGraph {
-    callers: HashMap<String, Vec<(PathBuf, usize, String)>>,
-    callees: HashMap<String, Vec<(PathBuf, usize, String)>>,
+    pub(crate) callers: HashMap<String, Vec<(PathBuf, usize, String)>>,
+    pub(crate) callees: HashMap<String, Vec<(PathBuf, usize, String)>>,
     pub definitions: HashMap<String, Vec<(PathBuf, usize)>>,
+    /// Definitions keyed by (module path, name), derived from each file's location
+    qualified_definitions: HashMap<(String, String), Vec<(PathBuf, usize)>>,
+    /// Per-file import tables: local alias -> fully-qualified path
+    imports: HashMap<PathBuf, HashMap<String, String>>,
+    /// Callee names that resolved to more than one candidate definition
+    unresolved_callees: HashSet<String>,
+    /// Method definitions keyed by (associated type, method name), e.g. `(Parser, parse)`
+    method_definitions: HashMap<(String, String), Vec<(PathBuf, usize)>>,
 }
 
 impl CallGraph {
@@ -22,16 +38,230 @@ impl CallGraph {
         Self::default()
     }
 
+    /// Derive a module path from a file's location, e.g. `src/analyze/graph.rs` -> `analyze::graph`
+    fn module_path_for(file: &Path) -> String {
+        let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+
+        let mut parts: Vec<String> = file
+            .parent()
+            .map(|parent| {
+                parent
+                    .components()
+                    .filter_map(|c| c.as_os_str().to_str())
+                    .filter(|c| *c != "src")
+                    .map(|c| c.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !stem.is_empty() && stem != "mod" && stem != "lib" {
+            parts.push(stem.to_string());
+        }
+
+        parts.join("::")
+    }
+
+    fn qualify(module: &str, name: &str) -> String {
+        if module.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}::{}", module, name)
+        }
+    }
+
+    fn join_path(prefix: &str, segment: &str) -> String {
+        if prefix.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}::{}", prefix, segment)
+        }
+    }
+
+    /// Parse a file's raw `use`-declaration text into an alias -> fully-qualified path table
+    fn parse_imports(raw_imports: &[String]) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        for raw in raw_imports {
+            let body = raw.trim().trim_start_matches("use ").trim_end_matches(';');
+            Self::parse_use_path(body.trim(), "", &mut map);
+        }
+        map
+    }
+
+    fn parse_use_path(segment: &str, prefix: &str, map: &mut HashMap<String, String>) {
+        let segment = segment.trim();
+        if segment.is_empty() || segment == "*" {
+            return;
+        }
+
+        if let Some(brace_start) = segment.find('{') {
+            let head = segment[..brace_start].trim().trim_end_matches("::").trim();
+            let base = Self::join_path(prefix, head);
+            let inner = segment[brace_start + 1..].trim_end_matches('}');
+            for part in inner.split(',') {
+                Self::parse_use_path(part.trim(), &base, map);
+            }
+            return;
+        }
+
+        if let Some((path, alias)) = segment.split_once(" as ") {
+            map.insert(
+                alias.trim().to_string(),
+                Self::join_path(prefix, path.trim()),
+            );
+            return;
+        }
+
+        let full = Self::join_path(prefix, segment);
+        let name = full.rsplit("::").next().unwrap_or(&full).to_string();
+        map.insert(name, full);
+    }
+
+    /// Record a caller -> callee edge in `callees`, keyed by both the bare caller name (so a
+    /// direct, unqualified `--focus` lookup still finds it) and its module-qualified form (so a
+    /// deeper BFS hop that already resolved to the qualified name — see
+    /// [`Self::find_outgoing_chains`] — can keep matching), mirroring the bare/qualified dual
+    /// keying [`Self::build_from_results`] already does for callees in `callers`.
+    fn record_callee_edge(
+        callees: &mut HashMap<String, Vec<(PathBuf, usize, String)>>,
+        bare_caller: &str,
+        qualified_caller: &str,
+        file: &Path,
+        line: usize,
+        callee: &str,
+    ) {
+        callees
+            .entry(bare_caller.to_string())
+            .or_default()
+            .push((file.to_path_buf(), line, callee.to_string()));
+        if qualified_caller != bare_caller {
+            callees
+                .entry(qualified_caller.to_string())
+                .or_default()
+                .push((file.to_path_buf(), line, callee.to_string()));
+        }
+    }
+
+    /// Resolve a scoped call like `Type::method` (or, through an import alias, `Alias::method`)
+    /// against [`Self::method_definitions`], the table [`Self::build_from_results`] populates by
+    /// matching a `ReferenceType::MethodDefinition`'s `associated_type` to its real function
+    /// definition. Split on `::` alone (not Rust-specific syntax), so the same lookup can back
+    /// a receiver-typed call in any language whose `find_method_for_receiver_handler` resolves a
+    /// receiver expression to its declared type before handing the name to the call graph.
+    fn resolve_scoped_callee(&self, file: &Path, callee: &str) -> Option<String> {
+        let (type_part, method_part) = callee.rsplit_once("::")?;
+
+        let aliased = self
+            .imports
+            .get(file)
+            .and_then(|imports| imports.get(type_part))
+            .map(|qualified| qualified.rsplit("::").next().unwrap_or(qualified).to_string());
+
+        aliased
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(type_part))
+            .find(|candidate_type| {
+                self.method_definitions
+                    .contains_key(&(candidate_type.to_string(), method_part.to_string()))
+            })
+            .map(|candidate_type| Self::qualify(candidate_type, method_part))
+    }
+
+    /// Resolve an unqualified callee name against same-file definitions, then imports,
+    /// then the global definition table (falling back to ambiguous/unresolved).
+    fn resolve_callee(
+        &self,
+        file: &Path,
+        file_module: &str,
+        same_file_names: &HashSet<String>,
+        callee: &str,
+    ) -> CalleeResolution {
+        if let Some(resolved) = self.resolve_scoped_callee(file, callee) {
+            return CalleeResolution::Resolved(resolved);
+        }
+
+        if same_file_names.contains(callee) {
+            return CalleeResolution::Resolved(Self::qualify(file_module, callee));
+        }
+
+        if let Some(imports) = self.imports.get(file)
+            && let Some(qualified) = imports.get(callee)
+        {
+            return CalleeResolution::Resolved(qualified.clone());
+        }
+
+        let candidates: Vec<String> = self
+            .qualified_definitions
+            .keys()
+            .filter(|(_, name)| name == callee)
+            .map(|(module, name)| Self::qualify(module, name))
+            .collect();
+
+        match candidates.len() {
+            0 => CalleeResolution::Unresolved,
+            1 => CalleeResolution::Resolved(candidates[0].clone()),
+            _ => CalleeResolution::Ambiguous(candidates),
+        }
+    }
+
+    /// Whether a callee name was seen with more than one resolvable candidate definition
+    pub fn is_unresolved(&self, callee: &str) -> bool {
+        self.unresolved_callees.contains(callee)
+    }
+
+    /// Whether `name` is conventionally reachable without an explicit call edge: a process
+    /// entry point, or a test function/method discovered by its naming convention. Any leaf
+    /// name containing `test` (case-insensitive) counts, which covers the `test_`/`Test`
+    /// prefixes and `test` suffix used across Rust, Python, Go and Java, and also matches
+    /// non-test names such as `latest`.
+    fn is_conventional_entry_point(name: &str) -> bool {
+        let leaf = name.rsplit("::").next().unwrap_or(name);
+        leaf == "main" || leaf.to_lowercase().contains("test")
+    }
+
+    /// List every defined function/class/method with no incoming caller or type-reference
+    /// edge, excluding conventional entry points (`main`, test functions). Mirrors the
+    /// reachability analysis rust-analyzer uses to flag never-used items, but understands
+    /// call and type-reference edges across the whole tree rather than a single file.
+    pub fn unreferenced_definitions(&self) -> Vec<(&str, &PathBuf, usize)> {
+        let mut unreferenced: Vec<(&str, &PathBuf, usize)> = self
+            .definitions
+            .iter()
+            .filter(|(name, _)| !self.callers.contains_key(name.as_str()))
+            .filter(|(name, _)| !Self::is_conventional_entry_point(name))
+            .flat_map(|(name, locations)| {
+                locations
+                    .iter()
+                    .map(move |(file, line)| (name.as_str(), file, *line))
+            })
+            .collect();
+
+        unreferenced.sort_by(|a, b| a.1.cmp(b.1).then(a.2.cmp(&b.2)).then(a.0.cmp(b.0)));
+        unreferenced
+    }
+
     pub fn build_from_results(results: &[(PathBuf, AnalysisResult)]) -> Self {
         let mut graph = Self::new();
 
+        // First pass: definitions and import tables, so call resolution below has the
+        // full picture of what each file can see regardless of processing order.
+        let mut same_file_names: HashMap<PathBuf, HashSet<String>> = HashMap::new();
         for (file_path, result) in results {
+            let module = Self::module_path_for(file_path);
+            let names = same_file_names.entry(file_path.clone()).or_default();
+
             for func in &result.functions {
                 graph
                     .definitions
                     .entry(func.name.clone())
                     .or_default()
                     .push((file_path.clone(), func.line));
+                graph
+                    .qualified_definitions
+                    .entry((module.clone(), func.name.clone()))
+                    .or_default()
+                    .push((file_path.clone(), func.line));
+                names.insert(func.name.clone());
             }
 
             for class in &result.classes {
@@ -40,26 +270,123 @@ impl CallGraph {
                     .entry(class.name.clone())
                     .or_default()
                     .push((file_path.clone(), class.line));
+                graph
+                    .qualified_definitions
+                    .entry((module.clone(), class.name.clone()))
+                    .or_default()
+                    .push((file_path.clone(), class.line));
+                names.insert(class.name.clone());
+            }
+
+            graph
+                .imports
+                .insert(file_path.clone(), Self::parse_imports(&result.imports));
+
+            // Associate each `MethodDefinition` reference (e.g. a `self` receiver) with the
+            // actual function definition it belongs to, so `Type::method` calls can resolve
+            // to where `method` is really defined rather than staying an opaque string.
+            for reference in &result.references {
+                if reference.ref_type != ReferenceType::MethodDefinition {
+                    continue;
+                }
+                let Some(type_name) = &reference.associated_type else {
+                    continue;
+                };
+                if let Some(func) = result.functions.iter().find(|f| f.name == reference.symbol) {
+                    let key = (type_name.clone(), reference.symbol.clone());
+                    graph
+                        .method_definitions
+                        .entry(key.clone())
+                        .or_default()
+                        .push((file_path.clone(), func.line));
+                    graph
+                        .definitions
+                        .entry(Self::qualify(type_name, &reference.symbol))
+                        .or_default()
+                        .push((file_path.clone(), func.line));
+                }
             }
+        }
+
+        for (file_path, result) in results {
+            let file_module = Self::module_path_for(file_path);
+            let empty_names = HashSet::new();
+            let names = same_file_names.get(file_path).unwrap_or(&empty_names);
 
             for call in &result.calls {
                 let caller = call
                     .caller_name
                     .clone()
                     .unwrap_or_else(|| "<module>".to_string());
+                // Qualify the caller with its own file's module, mirroring what resolve_callee
+                // already does for callees below — otherwise two files that happen to define a
+                // same-named function (e.g. both have `fn top()`) merge into one BFS node past
+                // the first hop, splicing unrelated call chains together.
+                let qualified_caller = if caller == "<module>" {
+                    caller.clone()
+                } else {
+                    Self::qualify(&file_module, &caller)
+                };
 
-                graph
-                    .callers
-                    .entry(call.callee_name.clone())
-                    .or_default()
-                    .push((file_path.clone(), call.line, caller.clone()));
+                graph.callers.entry(call.callee_name.clone()).or_default().push((
+                    file_path.clone(),
+                    call.line,
+                    qualified_caller.clone(),
+                ));
 
                 if caller != "<module>" {
-                    graph.callees.entry(caller).or_default().push((
-                        file_path.clone(),
+                    Self::record_callee_edge(
+                        &mut graph.callees,
+                        &caller,
+                        &qualified_caller,
+                        file_path,
                         call.line,
-                        call.callee_name.clone(),
-                    ));
+                        &call.callee_name,
+                    );
+                }
+
+                match graph.resolve_callee(file_path, &file_module, names, &call.callee_name) {
+                    CalleeResolution::Resolved(qualified) if qualified != call.callee_name => {
+                        graph.callers.entry(qualified.clone()).or_default().push((
+                            file_path.clone(),
+                            call.line,
+                            qualified_caller.clone(),
+                        ));
+                        if caller != "<module>" {
+                            Self::record_callee_edge(
+                                &mut graph.callees,
+                                &caller,
+                                &qualified_caller,
+                                file_path,
+                                call.line,
+                                &qualified,
+                            );
+                        }
+                    }
+                    CalleeResolution::Ambiguous(candidates) => {
+                        // Preserve today's behavior of flagging the callee as unresolved, but
+                        // still emit an edge per candidate so chains can be traced through any
+                        // of them rather than dead-ending at the ambiguous call site.
+                        graph.unresolved_callees.insert(call.callee_name.clone());
+                        for candidate in candidates {
+                            graph.callers.entry(candidate.clone()).or_default().push((
+                                file_path.clone(),
+                                call.line,
+                                qualified_caller.clone(),
+                            ));
+                            if caller != "<module>" {
+                                Self::record_callee_edge(
+                                    &mut graph.callees,
+                                    &caller,
+                                    &qualified_caller,
+                                    file_path,
+                                    call.line,
+                                    &candidate,
+                                );
+                            }
+                        }
+                    }
+                    _ => {}
                 }
             }
 
@@ -96,6 +423,135 @@ impl CallGraph {
         graph
     }
 
+    /// Single-row Levenshtein edit distance between `query` and `candidate`
+    fn levenshtein(query: &str, candidate: &str) -> usize {
+        let query: Vec<char> = query.chars().collect();
+        let mut row: Vec<usize> = (0..=query.len()).collect();
+
+        for (i, c) in candidate.chars().enumerate() {
+            let mut prev = row[0];
+            row[0] = i + 1;
+            for (j, q) in query.iter().enumerate() {
+                let deleted = row[j] + 1;
+                let inserted = row[j + 1] + 1;
+                let substituted = prev + if *q == c { 0 } else { 1 };
+                let current = row[j + 1];
+                row[j + 1] = deleted.min(inserted).min(substituted);
+                prev = current;
+            }
+        }
+
+        row[query.len()]
+    }
+
+    /// Look up a `--focus` symbol for definitions, preferring an exact `module::name` match
+    /// when the caller passed one (disambiguating same-named symbols in different files/modules
+    /// the way [`Self::qualified_definitions`] does internally) and otherwise falling back to
+    /// the bare-name bucket in [`Self::definitions`], which merges every file defining that name.
+    pub fn definitions_for(&self, focus: &str) -> Vec<(PathBuf, usize)> {
+        if let Some((module, name)) = focus.rsplit_once("::")
+            && let Some(locations) = self
+                .qualified_definitions
+                .get(&(module.to_string(), name.to_string()))
+        {
+            return locations.clone();
+        }
+
+        self.definitions.get(focus).cloned().unwrap_or_default()
+    }
+
+    /// Suggest candidate definitions for a focus symbol that wasn't found, the way
+    /// `cargo` suggests corrected subcommands via its `lev_distance` helper. Candidates
+    /// within `max(2, query.len() / 3)` edits are returned closest-first, capped at 5.
+    pub fn suggest(&self, query: &str) -> Vec<String> {
+        let threshold = (query.chars().count() / 3).max(2);
+
+        let mut candidates: Vec<(usize, &String)> = self
+            .definitions
+            .keys()
+            .filter(|name| name.as_str() != query)
+            .map(|name| (Self::levenshtein(query, name), name))
+            .filter(|(distance, _)| *distance <= threshold)
+            .collect();
+
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+        candidates
+            .into_iter()
+            .take(5)
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+
+    /// Every call site / usage of `symbol` across the tree: direct calls and type references,
+    /// deduplicated against the symbol's own definition sites so a recursive call recorded at
+    /// the definition line itself doesn't show up as an external reference.
+    pub fn references(&self, symbol: &str) -> Vec<(PathBuf, usize, String)> {
+        let def_sites: HashSet<(&PathBuf, usize)> = self
+            .definitions
+            .get(symbol)
+            .map(|locations| locations.iter().map(|(file, line)| (file, *line)).collect())
+            .unwrap_or_default();
+
+        let mut references: Vec<(PathBuf, usize, String)> = self
+            .callers
+            .get(symbol)
+            .into_iter()
+            .flatten()
+            .filter(|(file, line, _)| !def_sites.contains(&(file, *line)))
+            .map(|(file, line, caller)| (file.clone(), *line, caller.clone()))
+            .collect();
+
+        references.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+        references.dedup();
+        references
+    }
+
+    /// Compact `(inbound, outbound)` call summary for `symbol`: the distinct names that call
+    /// it, and the distinct names it calls, each sorted and deduplicated.
+    pub fn call_summary(&self, symbol: &str) -> (Vec<String>, Vec<String>) {
+        // Compare by leaf name, not the full string: `symbol` may be a bare name while callers
+        // are always stored module-qualified (see `build_from_results`), and self-recursion
+        // (the thing this exclusion targets) always shares a leaf with `symbol` regardless of
+        // which form either side happens to be in.
+        let symbol_leaf = symbol.rsplit("::").next().unwrap_or(symbol);
+
+        let mut inbound: Vec<String> = self
+            .callers
+            .get(symbol)
+            .into_iter()
+            .flatten()
+            .map(|(_, _, caller)| caller.clone())
+            .filter(|caller| {
+                caller != REFERENCE_CALLER
+                    && caller != "<module>"
+                    && caller.rsplit("::").next().unwrap_or(caller) != symbol_leaf
+            })
+            .collect();
+        inbound.sort();
+        inbound.dedup();
+
+        let mut outbound: Vec<String> = self
+            .callees
+            .get(symbol)
+            .into_iter()
+            .flatten()
+            .map(|(_, _, callee)| callee.clone())
+            .filter(|callee| callee.rsplit("::").next().unwrap_or(callee) != symbol_leaf)
+            .collect();
+        // A resolved callee is recorded both under its bare name and its qualified form (see
+        // `record_callee_edge`); once the qualified form is present, the bare one is redundant.
+        let qualified_leaves: HashSet<String> = outbound
+            .iter()
+            .filter(|callee| callee.contains("::"))
+            .map(|callee| callee.rsplit("::").next().unwrap_or(callee).to_string())
+            .collect();
+        outbound.retain(|callee| callee.contains("::") || !qualified_leaves.contains(callee.as_str()));
+        outbound.sort();
+        outbound.dedup();
+
+        (inbound, outbound)
+    }
+
     pub fn find_incoming_chains(&self, symbol: &str, max_depth: u32) -> Vec<CallChain> {
         if max_depth == 0 {
             return vec![];
@@ -331,4 +787,322 @@ mod tests {
         let graph = CallGraph::build_from_results(&results);
         assert!(graph.definitions.contains_key("MyStruct"));
     }
+
+    #[test]
+    fn module_path_strips_src_and_mod() {
+        assert_eq!(
+            CallGraph::module_path_for(Path::new("src/analyze/graph.rs")),
+            "analyze::graph"
+        );
+        assert_eq!(
+            CallGraph::module_path_for(Path::new("src/analyze/mod.rs")),
+            "analyze"
+        );
+        assert_eq!(CallGraph::module_path_for(Path::new("test.rs")), "test");
+    }
+
+    #[test]
+    fn parse_imports_handles_groups_and_aliases() {
+        let imports = CallGraph::parse_imports(&[
+            "use std::collections::HashMap;".to_string(),
+            "use crate::analyze::{graph::CallGraph, types::AnalysisResult as Result};".to_string(),
+        ]);
+        assert_eq!(imports.get("HashMap").unwrap(), "std::collections::HashMap");
+        assert_eq!(
+            imports.get("CallGraph").unwrap(),
+            "crate::analyze::graph::CallGraph"
+        );
+        assert_eq!(
+            imports.get("Result").unwrap(),
+            "crate::analyze::types::AnalysisResult"
+        );
+    }
+
+    #[test]
+    fn same_name_functions_in_different_files_resolve_separately() {
+        // Two files each define `run`, and each calls its own local `run` from `main`.
+        let mut file_a = make_result(&["run", "main"], &[("main", "run")]);
+        file_a.calls[0].line = 10;
+        let mut file_b = make_result(&["run", "main"], &[("main", "run")]);
+        file_b.calls[0].line = 20;
+
+        let results = vec![
+            (PathBuf::from("src/a.rs"), file_a),
+            (PathBuf::from("src/b.rs"), file_b),
+        ];
+        let graph = CallGraph::build_from_results(&results);
+
+        // Each file's call resolves to its own same-file definition, not the other file's.
+        let incoming = graph.find_incoming_chains("a::run", 1);
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].path[0].0, PathBuf::from("src/a.rs"));
+
+        let incoming = graph.find_incoming_chains("b::run", 1);
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].path[0].0, PathBuf::from("src/b.rs"));
+    }
+
+    #[test]
+    fn same_name_intermediate_callers_do_not_merge_chains_past_depth_one() {
+        // Two unrelated files each define `top` and call it from their own `entry`, and each
+        // `top` calls the shared `run`. A bare (unqualified) caller name would collapse
+        // `a::top`/`b::top`'s callers into one shared bucket keyed by "top" past the first hop,
+        // splicing file_a's entry point onto file_b's chain and vice versa.
+        let file_a = make_result(&["entry", "top"], &[("entry", "top"), ("top", "run")]);
+        let file_b = make_result(&["entry", "top"], &[("entry", "top"), ("top", "run")]);
+
+        let results = vec![
+            (PathBuf::from("src/a.rs"), file_a),
+            (PathBuf::from("src/b.rs"), file_b),
+        ];
+        let graph = CallGraph::build_from_results(&results);
+
+        let incoming = graph.find_incoming_chains("run", 2);
+        assert_eq!(incoming.len(), 2);
+        for chain in &incoming {
+            // The outermost hop's caller/callee must belong to the same file's module: an
+            // `a::entry -> a::top` or `b::entry -> b::top` hop, never a cross-file mix.
+            let (_, _, from, to) = &chain.path[0];
+            let from_module = from.rsplit_once("::").map(|(m, _)| m);
+            let to_module = to.rsplit_once("::").map(|(m, _)| m);
+            assert_eq!(from_module, to_module, "chain mixes modules: {chain:?}");
+        }
+    }
+
+    #[test]
+    fn scoped_call_resolves_to_method_definition() {
+        use crate::analyze::types::ReferenceInfo;
+
+        // impl Parser { fn parse(&self) {} }  /  use parser::Parser as P; fn main() { P::parse(&p); }
+        // The raw callee text is "P::parse", which matches nothing in `definitions` on its
+        // own — only resolving "P" through the caller's imports to "Parser" and then through
+        // `method_definitions` proves the alias is actually being followed.
+        let mut def_file = make_result(&["parse"], &[]);
+        def_file.references.push(ReferenceInfo {
+            symbol: "parse".to_string(),
+            ref_type: ReferenceType::MethodDefinition,
+            line: 1,
+            context: String::new(),
+            associated_type: Some("Parser".to_string()),
+        });
+
+        let mut caller_file = make_result(&["main"], &[("main", "P::parse")]);
+        caller_file
+            .imports
+            .push("use parser::Parser as P;".to_string());
+
+        let results = vec![
+            (PathBuf::from("src/parser.rs"), def_file),
+            (PathBuf::from("src/main.rs"), caller_file),
+        ];
+        let graph = CallGraph::build_from_results(&results);
+
+        // The aliased scoped call resolves to the actual method definition location...
+        assert_eq!(
+            graph.definitions["Parser::parse"],
+            vec![(PathBuf::from("src/parser.rs"), 1)]
+        );
+        // ...and shows up as an incoming chain under the resolved name.
+        let incoming = graph.find_incoming_chains("Parser::parse", 1);
+        assert!(
+            !incoming.is_empty(),
+            "expected the aliased call to resolve to Parser::parse"
+        );
+        // The raw "P::parse" callee text itself never resolves to a definition.
+        assert!(!graph.definitions.contains_key("P::parse"));
+    }
+
+    #[test]
+    fn ambiguous_cross_module_callee_is_flagged() {
+        // `helper` is defined in two unrelated modules; a third file calls it without
+        // defining or importing it locally, so the global fallback can't disambiguate.
+        let def_a = make_result(&["helper"], &[]);
+        let def_b = make_result(&["helper"], &[]);
+        let caller = make_result(&["main"], &[("main", "helper")]);
+
+        let results = vec![
+            (PathBuf::from("src/a.rs"), def_a),
+            (PathBuf::from("src/b.rs"), def_b),
+            (PathBuf::from("src/c.rs"), caller),
+        ];
+        let graph = CallGraph::build_from_results(&results);
+        assert!(graph.is_unresolved("helper"));
+
+        // Flagging the call as unresolved shouldn't stop a chain from tracing through either
+        // candidate definition: both `a::helper` and `b::helper` should show `c::main` as an
+        // incoming caller.
+        let incoming_a = graph.find_incoming_chains("a::helper", 1);
+        assert_eq!(incoming_a.len(), 1);
+        assert_eq!(incoming_a[0].path[0].2, "c::main");
+
+        let incoming_b = graph.find_incoming_chains("b::helper", 1);
+        assert_eq!(incoming_b.len(), 1);
+        assert_eq!(incoming_b[0].path[0].2, "c::main");
+    }
+
+    #[test]
+    fn unreferenced_definitions_excludes_called_functions() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["main", "used", "dead"], &[("main", "used")]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let names: Vec<&str> = graph
+            .unreferenced_definitions()
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+        assert_eq!(names, vec!["dead"]);
+    }
+
+    #[test]
+    fn unreferenced_definitions_skips_main_and_tests() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["main", "test_helper", "orphan"], &[]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let names: Vec<&str> = graph
+            .unreferenced_definitions()
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+        assert_eq!(names, vec!["orphan"]);
+    }
+
+    #[test]
+    fn unreferenced_definitions_respects_type_references() {
+        use crate::analyze::types::ReferenceInfo;
+
+        let mut result = make_result(&["main"], &[]);
+        result.classes.push(ClassInfo {
+            name: "Config".into(),
+            line: 5,
+            methods: vec![],
+        });
+        result.references.push(ReferenceInfo {
+            symbol: "Config".to_string(),
+            ref_type: ReferenceType::TypeInstantiation,
+            line: 10,
+            context: String::new(),
+            associated_type: None,
+        });
+
+        let results = vec![(PathBuf::from("test.rs"), result)];
+        let graph = CallGraph::build_from_results(&results);
+
+        let names: Vec<&str> = graph
+            .unreferenced_definitions()
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect();
+        assert!(!names.contains(&"Config"));
+    }
+
+    #[test]
+    fn suggest_finds_close_matches() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["helper", "help", "unrelated_name"], &[]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let suggestions = graph.suggest("helpr");
+        assert_eq!(suggestions, vec!["help", "helper"]);
+    }
+
+    #[test]
+    fn suggest_excludes_exact_match_and_distant_names() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["helper", "completely_unrelated"], &[]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let suggestions = graph.suggest("helper");
+        assert!(!suggestions.contains(&"helper".to_string()));
+        assert!(!suggestions.contains(&"completely_unrelated".to_string()));
+    }
+
+    #[test]
+    fn suggest_caps_at_five_sorted_by_distance_then_name() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(
+                &["fooa", "foob", "fooc", "food", "fooe", "foof", "foo"],
+                &[],
+            ),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let suggestions = graph.suggest("foo");
+        assert_eq!(suggestions.len(), 5);
+        assert_eq!(suggestions[0], "fooa");
+    }
+
+    #[test]
+    fn references_lists_call_sites_excluding_definition_line() {
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["main", "helper"], &[("main", "helper")]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let references = graph.references("helper");
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].0, PathBuf::from("test.rs"));
+        // The caller is recorded module-qualified, not bare (see `build_from_results`).
+        assert_eq!(references[0].2, "test::main");
+
+        // The definition line itself is never reported as a reference.
+        assert!(
+            !graph
+                .references("helper")
+                .iter()
+                .any(|(_, line, _)| *line == 2)
+        );
+    }
+
+    #[test]
+    fn references_empty_for_unreferenced_symbol() {
+        let results = vec![(PathBuf::from("test.rs"), make_result(&["lonely"], &[]))];
+        let graph = CallGraph::build_from_results(&results);
+        assert!(graph.references("lonely").is_empty());
+    }
+
+    #[test]
+    fn call_summary_reports_inbound_and_outbound() {
+        // a -> b -> c
+        let results = vec![(
+            PathBuf::from("test.rs"),
+            make_result(&["a", "b", "c"], &[("a", "b"), ("b", "c")]),
+        )];
+        let graph = CallGraph::build_from_results(&results);
+
+        let (inbound, outbound) = graph.call_summary("b");
+        // Both directions are reported module-qualified (see `build_from_results`).
+        assert_eq!(inbound, vec!["test::a".to_string()]);
+        assert_eq!(outbound, vec!["test::c".to_string()]);
+    }
+
+    #[test]
+    fn call_summary_excludes_module_scope_and_self_recursion() {
+        let mut result = make_result(&["recurse"], &[("recurse", "recurse")]);
+        // A top-level call outside any function is recorded with the `<module>` caller.
+        result.calls.push(CallInfo {
+            caller_name: None,
+            callee_name: "recurse".to_string(),
+            line: 20,
+            column: 0,
+            context: String::new(),
+        });
+        let results = vec![(PathBuf::from("test.rs"), result)];
+        let graph = CallGraph::build_from_results(&results);
+
+        let (inbound, outbound) = graph.call_summary("recurse");
+        assert!(inbound.is_empty());
+        assert!(outbound.is_empty());
+    }
 }
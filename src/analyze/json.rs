@@ -0,0 +1,292 @@
+// Copyright 2024 Block, Inc. (original code from https://github.com/block/goose)
+// Copyright 2025 utapyngo (modifications)
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::graph::CallGraph;
+use super::project::ProjectModel;
+use super::types::{AnalysisResult, CallChain, EntryType};
+
+/// Render a path relative to `cwd` for stable, portable JSON output
+fn relative(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonLocation {
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonEdge {
+    pub file: String,
+    pub line: usize,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonChainStep {
+    pub file: String,
+    pub line: usize,
+    pub from: String,
+    pub to: String,
+}
+
+fn chain_to_json(chain: &CallChain, cwd: &Path) -> Vec<JsonChainStep> {
+    chain
+        .path
+        .iter()
+        .map(|(file, line, from, to)| JsonChainStep {
+            file: relative(file, cwd),
+            line: *line,
+            from: from.clone(),
+            to: to.clone(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonCallGraph {
+    pub definitions: HashMap<String, Vec<JsonLocation>>,
+    pub callers: HashMap<String, Vec<JsonEdge>>,
+    pub callees: HashMap<String, Vec<JsonEdge>>,
+}
+
+impl JsonCallGraph {
+    pub fn from_graph(graph: &CallGraph, cwd: &Path) -> Self {
+        let definitions = graph
+            .definitions
+            .iter()
+            .map(|(name, locations)| {
+                let locations = locations
+                    .iter()
+                    .map(|(file, line)| JsonLocation {
+                        file: relative(file, cwd),
+                        line: *line,
+                    })
+                    .collect();
+                (name.clone(), locations)
+            })
+            .collect();
+
+        type RawEdges = HashMap<String, Vec<(PathBuf, usize, String)>>;
+        let to_edges = |edges: &RawEdges| -> HashMap<String, Vec<JsonEdge>> {
+            edges
+                .iter()
+                .map(|(name, entries)| {
+                    let entries = entries
+                        .iter()
+                        .map(|(file, line, symbol)| JsonEdge {
+                            file: relative(file, cwd),
+                            line: *line,
+                            symbol: symbol.clone(),
+                        })
+                        .collect();
+                    (name.clone(), entries)
+                })
+                .collect()
+        };
+
+        Self {
+            definitions,
+            callers: to_edges(&graph.callers),
+            callees: to_edges(&graph.callees),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonFocusedAnalysis {
+    pub focus_symbol: String,
+    pub follow_depth: u32,
+    pub files_analyzed: Vec<String>,
+    pub definitions: Vec<JsonLocation>,
+    pub incoming_chains: Vec<Vec<JsonChainStep>>,
+    pub outgoing_chains: Vec<Vec<JsonChainStep>>,
+    /// Closest-spelled candidate names, populated only when `definitions` is empty
+    pub suggestions: Vec<String>,
+}
+
+impl JsonFocusedAnalysis {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        focus_symbol: &str,
+        follow_depth: u32,
+        files_analyzed: &[PathBuf],
+        definitions: &[(PathBuf, usize)],
+        incoming_chains: &[CallChain],
+        outgoing_chains: &[CallChain],
+        suggestions: &[String],
+        cwd: &Path,
+    ) -> Self {
+        Self {
+            focus_symbol: focus_symbol.to_string(),
+            follow_depth,
+            files_analyzed: files_analyzed.iter().map(|p| relative(p, cwd)).collect(),
+            definitions: definitions
+                .iter()
+                .map(|(file, line)| JsonLocation {
+                    file: relative(file, cwd),
+                    line: *line,
+                })
+                .collect(),
+            incoming_chains: incoming_chains
+                .iter()
+                .map(|chain| chain_to_json(chain, cwd))
+                .collect(),
+            outgoing_chains: outgoing_chains
+                .iter()
+                .map(|chain| chain_to_json(chain, cwd))
+                .collect(),
+            suggestions: if definitions.is_empty() {
+                suggestions.to_vec()
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonFileEntry {
+    pub path: String,
+    pub result: AnalysisResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonPackage {
+    pub name: String,
+    pub root: String,
+    pub manifest: String,
+    pub dependencies: Vec<String>,
+    pub line_count: usize,
+    pub function_count: usize,
+}
+
+pub fn packages_to_json(model: &ProjectModel, cwd: &Path) -> Vec<JsonPackage> {
+    model
+        .packages
+        .iter()
+        .map(|pkg| JsonPackage {
+            name: pkg.name.clone(),
+            root: relative(&pkg.root, cwd),
+            manifest: relative(&pkg.manifest, cwd),
+            dependencies: pkg.dependencies.clone(),
+            line_count: pkg.line_count,
+            function_count: pkg.function_count,
+        })
+        .collect()
+}
+
+pub fn directory_results_to_json(
+    results: &[(PathBuf, EntryType)],
+    cwd: &Path,
+) -> Vec<JsonFileEntry> {
+    results
+        .iter()
+        .map(|(path, EntryType::File(result))| JsonFileEntry {
+            path: relative(path, cwd),
+            result: result.clone(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+pub enum JsonOutput {
+    File {
+        path: String,
+        result: AnalysisResult,
+    },
+    Directory {
+        path: String,
+        files: Vec<JsonFileEntry>,
+        packages: Vec<JsonPackage>,
+    },
+    Focused {
+        focus: JsonFocusedAnalysis,
+        graph: JsonCallGraph,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::types::AnalysisResult;
+
+    #[test]
+    fn relative_strips_cwd_prefix() {
+        let cwd = Path::new("/repo");
+        assert_eq!(relative(Path::new("/repo/src/main.rs"), cwd), "src/main.rs");
+    }
+
+    #[test]
+    fn relative_falls_back_to_original_outside_cwd() {
+        let cwd = Path::new("/repo");
+        assert_eq!(relative(Path::new("/other/main.rs"), cwd), "/other/main.rs");
+    }
+
+    #[test]
+    fn packages_to_json_converts_and_relativizes() {
+        use super::super::project::PackageInfo;
+
+        let model = ProjectModel {
+            packages: vec![PackageInfo {
+                name: "my-crate".to_string(),
+                manifest: PathBuf::from("/repo/Cargo.toml"),
+                root: PathBuf::from("/repo"),
+                dependencies: vec!["serde".to_string()],
+                line_count: 10,
+                function_count: 2,
+            }],
+        };
+
+        let packages = packages_to_json(&model, Path::new("/repo"));
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "my-crate");
+        assert_eq!(packages[0].root, "");
+        assert_eq!(packages[0].manifest, "Cargo.toml");
+        assert_eq!(packages[0].line_count, 10);
+    }
+
+    #[test]
+    fn json_call_graph_from_empty_graph() {
+        let graph = CallGraph::new();
+        let json = JsonCallGraph::from_graph(&graph, Path::new("/repo"));
+        assert!(json.definitions.is_empty());
+        assert!(json.callers.is_empty());
+        assert!(json.callees.is_empty());
+    }
+
+    #[test]
+    fn directory_results_to_json_converts_entries() {
+        let results = vec![(
+            PathBuf::from("/repo/src/main.rs"),
+            EntryType::File(AnalysisResult::empty(5)),
+        )];
+        let files = directory_results_to_json(&results, Path::new("/repo"));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "src/main.rs");
+        assert_eq!(files[0].result.line_count, 5);
+    }
+
+    #[test]
+    fn output_serializes_with_mode_tag() {
+        let output = JsonOutput::Error {
+            message: "boom".to_string(),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"mode\":\"error\""));
+        assert!(json.contains("\"message\":\"boom\""));
+    }
+}
@@ -3,21 +3,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod cache;
+pub mod config;
 pub mod formatter;
 pub mod graph;
+pub mod json;
 pub mod languages;
 pub mod parser;
+pub mod progress;
+pub mod project;
+pub mod snippet;
 pub mod traversal;
 pub mod types;
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use self::cache::AnalysisCache;
+use self::cache::{AnalysisCache, ContentCache};
+use self::config::AnalysisConfig;
 use self::formatter::Formatter;
 use self::graph::CallGraph;
+use self::json::{
+    JsonCallGraph, JsonFocusedAnalysis, JsonOutput, directory_results_to_json, packages_to_json,
+};
 use self::parser::{ElementExtractor, ParserManager};
-use self::traversal::FileTraverser;
-use self::types::{AnalysisMode, AnalysisResult, FocusedAnalysisData};
+use self::progress::{ProgressCallback, ProgressData, ProgressReporter};
+use self::project::ProjectModel;
+use self::traversal::{FileTraverser, SkippedEntry, TraversalError};
+use self::types::{AnalysisMode, AnalysisResult, EntryType, FocusedAnalysisData};
 
 use crate::lang;
 
@@ -41,6 +54,9 @@ where
 pub struct CodeAnalyzer {
     parser_manager: ParserManager,
     cache: AnalysisCache,
+    progress: Option<ProgressCallback>,
+    stop_flag: Option<Arc<AtomicBool>>,
+    config: Option<Arc<AnalysisConfig>>,
 }
 
 impl Default for CodeAnalyzer {
@@ -54,9 +70,53 @@ impl CodeAnalyzer {
         Self {
             parser_manager: ParserManager::new(),
             cache: AnalysisCache::new(100),
+            progress: None,
+            stop_flag: None,
+            config: None,
         }
     }
 
+    /// Persist results to a content-hashed on-disk cache under `cache_dir`
+    pub fn with_disk_cache(mut self, cache_dir: PathBuf) -> Self {
+        self.cache = AnalysisCache::with_disk(cache_dir, 100);
+        self
+    }
+
+    /// Persist results to a single versioned binary file under `cache_dir`, keyed by path and
+    /// modification time instead of content hash
+    pub fn with_mtime_cache(mut self, cache_dir: PathBuf) -> Self {
+        self.cache = AnalysisCache::with_mtime_disk(cache_dir, 100);
+        self
+    }
+
+    /// Use `config`'s per-language toggles and `ast_recursion_limit` as defaults for callers
+    /// that don't pass their own (see [`AnalysisConfig::language_enabled`])
+    pub fn with_config(mut self, config: AnalysisConfig) -> Self {
+        self.config = Some(Arc::new(config));
+        self
+    }
+
+    /// Same as [`Self::new`] followed by [`Self::with_config`], but loads the config (with its
+    /// `include` layers) from `path` first
+    pub fn from_config(path: &Path) -> Result<Self, String> {
+        let config = AnalysisConfig::load(path)?;
+        Ok(Self::new().with_config(config))
+    }
+
+    /// Receive [`ProgressData`] updates while analyzing files in parallel (focused-analysis
+    /// mode), throttled to roughly one emission per 100ms.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Check `stop_flag` inside the parallel analysis loop so a consumer on another thread can
+    /// abort a run in progress.
+    pub fn with_stop_flag(mut self, stop_flag: Arc<AtomicBool>) -> Self {
+        self.stop_flag = Some(stop_flag);
+        self
+    }
+
     fn determine_mode(&self, focus: &Option<String>, path: &Path) -> AnalysisMode {
         if focus.is_some() {
             return AnalysisMode::Focused;
@@ -75,20 +135,8 @@ impl CodeAnalyzer {
         mode: &AnalysisMode,
         ast_recursion_limit: Option<usize>,
     ) -> Result<AnalysisResult, String> {
-        let metadata = std::fs::metadata(path)
-            .map_err(|e| format!("Failed to get metadata for '{}': {}", path.display(), e))?;
-
-        let modified = metadata.modified().map_err(|e| {
-            format!(
-                "Failed to get modification time for '{}': {}",
-                path.display(),
-                e
-            )
-        })?;
-
-        if let Some(cached) = self.cache.get(path, modified, mode) {
-            return Ok(cached);
-        }
+        let ast_recursion_limit = ast_recursion_limit
+            .or_else(|| self.config.as_ref().and_then(|c| c.ast_recursion_limit));
 
         let content = match std::fs::read_to_string(path) {
             Ok(content) => content,
@@ -98,12 +146,21 @@ impl CodeAnalyzer {
         };
 
         let line_count = content.lines().count();
+        let content_hash = ContentCache::hash_content(&content);
+
+        if let Some(cached) = self.cache.get(path, content_hash, mode, ast_recursion_limit) {
+            return Ok(cached);
+        }
 
         let language = lang::get_language_identifier(path);
         if language.is_empty() {
             return Ok(AnalysisResult::empty(line_count));
         }
 
+        if !self.config.as_ref().map(|c| c.language_enabled(language)).unwrap_or(true) {
+            return Ok(AnalysisResult::empty(line_count));
+        }
+
         let language_supported = languages::get_language_info(language)
             .map(|info| !info.element_query.is_empty())
             .unwrap_or(false);
@@ -125,29 +182,58 @@ impl CodeAnalyzer {
 
         result.line_count = line_count;
 
-        self.cache
-            .put(path.to_path_buf(), modified, mode, result.clone());
+        self.cache.put(
+            path.to_path_buf(),
+            content_hash,
+            mode,
+            ast_recursion_limit,
+            result.clone(),
+        );
 
         Ok(result)
     }
 
-    fn analyze_directory(
+    fn analyze_directory_raw(
         &self,
         path: &Path,
         max_depth: u32,
         ast_recursion_limit: Option<usize>,
         traverser: &FileTraverser,
         mode: &AnalysisMode,
-    ) -> Result<String, String> {
+    ) -> Result<(Vec<(PathBuf, EntryType)>, Vec<SkippedEntry>), String> {
         let mode = *mode;
 
-        let results = traverser.collect_directory_results(path, max_depth, |file_path| {
+        traverser.collect_directory_results(path, max_depth, |file_path| {
             self.analyze_file(file_path, &mode, ast_recursion_limit)
-        })?;
+        })
+    }
 
-        Ok(Formatter::format_directory_structure(
-            path, &results, max_depth,
-        ))
+    fn analyze_directory(
+        &self,
+        path: &Path,
+        max_depth: u32,
+        ast_recursion_limit: Option<usize>,
+        traverser: &FileTraverser,
+        mode: &AnalysisMode,
+    ) -> Result<String, String> {
+        let (results, skipped) =
+            self.analyze_directory_raw(path, max_depth, ast_recursion_limit, traverser, mode)?;
+
+        let mut output = Formatter::format_directory_structure(path, &results, max_depth);
+        output.push_str(&ProjectModel::discover(path, &results).format_packages_section(path));
+
+        if !skipped.is_empty() {
+            output.push_str("\nSKIPPED:\n");
+            for entry in &skipped {
+                let reason = match entry.error {
+                    TraversalError::InfiniteRecursion => "symlink cycle or too many jumps",
+                    TraversalError::NonExistentFile => "dangling symlink",
+                };
+                output.push_str(&format!("  {} ({})\n", entry.path.display(), reason));
+            }
+        }
+
+        Ok(output)
     }
 
     fn analyze_focused(
@@ -158,19 +244,46 @@ impl CodeAnalyzer {
         max_depth: u32,
         ast_recursion_limit: Option<usize>,
         traverser: &FileTraverser,
-    ) -> Result<String, String> {
+    ) -> Result<(String, Vec<(PathBuf, usize)>), String> {
         let files_to_analyze = if path.is_file() {
             vec![path.to_path_buf()]
         } else {
             traverser.collect_files_for_focused(path, max_depth)?
         };
 
+        let reporter = ProgressReporter::new(self.progress.clone());
+        let total = files_to_analyze.len();
+        reporter.emit(ProgressData {
+            current_stage: 1,
+            max_stage: 2,
+            entries_to_check: total,
+            entries_checked: 0,
+        });
+
         use rayon::prelude::*;
-        let all_results: Result<Vec<_>, _> = files_to_analyze
+        let entries_checked = AtomicUsize::new(0);
+        let all_results: Result<Vec<_>, String> = files_to_analyze
             .par_iter()
             .map(|file_path| {
-                self.analyze_file(file_path, &AnalysisMode::Semantic, ast_recursion_limit)
-                    .map(|result| (file_path.clone(), result))
+                if let Some(stop_flag) = &self.stop_flag
+                    && stop_flag.load(Ordering::Relaxed)
+                {
+                    return Err("Analysis stopped".to_string());
+                }
+
+                let result = self
+                    .analyze_file(file_path, &AnalysisMode::Semantic, ast_recursion_limit)
+                    .map(|result| (file_path.clone(), result));
+
+                let checked = entries_checked.fetch_add(1, Ordering::Relaxed) + 1;
+                reporter.emit_throttled(ProgressData {
+                    current_stage: 2,
+                    max_stage: 2,
+                    entries_to_check: total,
+                    entries_checked: checked,
+                });
+
+                result
             })
             .collect();
         let all_results = all_results?;
@@ -189,7 +302,7 @@ impl CodeAnalyzer {
             vec![]
         };
 
-        let definitions = graph.definitions.get(focus).cloned().unwrap_or_default();
+        let definitions = graph.definitions_for(focus);
 
         let focus_data = FocusedAnalysisData {
             focus_symbol: focus,
@@ -202,13 +315,75 @@ impl CodeAnalyzer {
 
         let mut output = Formatter::format_focused_output(&focus_data);
 
+        if definitions.is_empty() {
+            output = Self::inject_did_you_mean(output, &graph, focus);
+        } else {
+            output = Self::append_references_section(output, &graph, focus);
+        }
+
         if path.is_file() {
             let hint = "NOTE: Focus mode works best with directory paths. \
                         Use a parent directory in the path for cross-file analysis.\n\n";
             output = format!("{}{}", hint, output);
         }
 
-        Ok(output)
+        Ok((output, definitions))
+    }
+
+    /// Insert a `DID YOU MEAN:` block under the focused-analysis header when `focus` has
+    /// no definitions but close-spelled candidates exist elsewhere in the tree.
+    fn inject_did_you_mean(output: String, graph: &CallGraph, focus: &str) -> String {
+        let suggestions = graph.suggest(focus);
+        if suggestions.is_empty() {
+            return output;
+        }
+
+        let block: String = suggestions.iter().map(|s| format!("  {}\n", s)).collect();
+        let block = format!("DID YOU MEAN:\n{}\n", block);
+
+        match output.find('\n') {
+            Some(newline_pos) => {
+                let mut output = output;
+                output.insert_str(newline_pos + 1, &block);
+                output
+            }
+            None => format!("{}\n{}", output, block),
+        }
+    }
+
+    /// Append a `REFERENCES:` section listing every call site / usage of `focus` across the
+    /// analyzed tree, plus a compact inbound/outbound call summary, after the formatter's own
+    /// definition/chain sections.
+    fn append_references_section(mut output: String, graph: &CallGraph, focus: &str) -> String {
+        let references = graph.references(focus);
+        let (inbound, outbound) = graph.call_summary(focus);
+
+        output.push_str("\nREFERENCES:\n");
+        if references.is_empty() {
+            output.push_str("  (no external call sites found)\n");
+        } else {
+            for (file, line, caller) in &references {
+                let caller = match caller.as_str() {
+                    "<reference>" => "type reference",
+                    "<module>" => "module scope",
+                    name => name,
+                };
+                output.push_str(&format!("  {}:{} (in {})\n", file.display(), line, caller));
+            }
+        }
+
+        let join_or_none = |names: &[String]| {
+            if names.is_empty() {
+                "(none)".to_string()
+            } else {
+                names.join(", ")
+            }
+        };
+        output.push_str("\nCALL SUMMARY:\n");
+        output.push_str(&format!("  inbound:  {}\n", join_or_none(&inbound)));
+        output.push_str(&format!("  outbound: {}\n", join_or_none(&outbound)));
+
+        output
     }
 }
 
@@ -229,14 +404,555 @@ pub fn analyze(
     ast_recursion_limit: Option<usize>,
     cwd: &str,
 ) -> String {
+    analyze_with(
+        get_analyzer(),
+        path,
+        focus,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cwd,
+        FileTraverser::new(),
+    )
+}
+
+/// Same as [`analyze`], but persists results to a content-hashed on-disk cache under
+/// `cache_dir` (default: `.code-analyzer-cache` next to the analyzed path) unless `no_cache`
+/// is set.
+pub fn analyze_with_cache(
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: u32,
+    max_depth: u32,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+) -> String {
+    if no_cache {
+        return analyze(path, focus, follow_depth, max_depth, ast_recursion_limit, cwd);
+    }
+
     let abs_path = if Path::new(path).is_absolute() {
         PathBuf::from(path)
     } else {
         PathBuf::from(cwd).join(path)
     };
 
-    let analyzer = get_analyzer();
-    let traverser = FileTraverser::new();
+    let default_dir = if abs_path.is_file() {
+        abs_path
+            .parent()
+            .unwrap_or(&abs_path)
+            .join(".code-analyzer-cache")
+    } else {
+        abs_path.join(".code-analyzer-cache")
+    };
+    let cache_dir = cache_dir.map(PathBuf::from).unwrap_or(default_dir);
+
+    let analyzer = CodeAnalyzer::new().with_disk_cache(cache_dir);
+    analyze_with(
+        &analyzer,
+        path,
+        focus,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cwd,
+        FileTraverser::new(),
+    )
+}
+
+/// Parameters every config-aware `analyze_*` entry point (not just [`analyze_configured`])
+/// accepts so a discovered [`config::CONFIG_FILE_NAME`] and the on-disk cache are available
+/// regardless of which CLI mode (`--unreferenced`, `--focus`+`--context-lines`, `--output json`,
+/// or the default) the user picked.
+struct ConfiguredContext {
+    follow_depth: u32,
+    max_depth: u32,
+    ast_recursion_limit: Option<usize>,
+    analyzer: CodeAnalyzer,
+    traverser: FileTraverser,
+}
+
+/// Discover a [`config::CONFIG_FILE_NAME`] by walking up from `abs_path` (see
+/// [`AnalysisConfig::discover`]) and use it to fill in whichever of
+/// `follow_depth`/`max_depth`/`ast_recursion_limit`/`include`/`exclude`/`mtime_cache` the caller
+/// leaves unset (`None`, empty, or `false`), building the resulting cache-configured
+/// [`CodeAnalyzer`] and glob-filtering [`FileTraverser`] in the process — any value the caller
+/// does pass always wins over the config file.
+#[allow(clippy::too_many_arguments)]
+fn configured_context(
+    abs_path: &Path,
+    follow_depth: Option<u32>,
+    max_depth: Option<u32>,
+    ast_recursion_limit: Option<usize>,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+    mtime_cache: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> Result<ConfiguredContext, String> {
+    let config = match AnalysisConfig::discover(abs_path) {
+        Some(config_path) => {
+            AnalysisConfig::load(&config_path).map_err(|e| format!("Config error: {}", e))?
+        }
+        None => AnalysisConfig::default(),
+    };
+
+    let follow_depth = follow_depth.or(config.follow_depth).unwrap_or(2);
+    let max_depth = max_depth.or(config.max_depth).unwrap_or(3);
+    let ast_recursion_limit = ast_recursion_limit.or(config.ast_recursion_limit);
+    let include = if include.is_empty() { config.include.clone() } else { include };
+    let exclude = if exclude.is_empty() { config.exclude.clone() } else { exclude };
+    let mtime_cache = mtime_cache || config.mtime_cache.unwrap_or(false);
+
+    let default_dir = if abs_path.is_file() {
+        abs_path.parent().unwrap_or(abs_path).join(".code-analyzer-cache")
+    } else {
+        abs_path.join(".code-analyzer-cache")
+    };
+    let cache_dir = cache_dir.map(PathBuf::from).unwrap_or(default_dir);
+
+    let mut analyzer = CodeAnalyzer::new().with_config(config);
+    if !no_cache {
+        analyzer = if mtime_cache {
+            analyzer.with_mtime_cache(cache_dir)
+        } else {
+            analyzer.with_disk_cache(cache_dir)
+        };
+    }
+
+    let traverser = FileTraverser::new().with_include(include).with_exclude(exclude);
+
+    Ok(ConfiguredContext { follow_depth, max_depth, ast_recursion_limit, analyzer, traverser })
+}
+
+fn abs_path_for(path: &str, cwd: &str) -> PathBuf {
+    if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        PathBuf::from(cwd).join(path)
+    }
+}
+
+/// Same as [`analyze_with_cache`], but first discovers a [`config::CONFIG_FILE_NAME`] file by
+/// walking up from `path` (see [`AnalysisConfig::discover`]) and uses it to fill in whichever
+/// of `follow_depth`/`max_depth`/`ast_recursion_limit`/`include`/`exclude`/`mtime_cache` the
+/// caller leaves unset (`None`, empty, or `false`) — any value the caller does pass always wins
+/// over the config file.
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_configured(
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: Option<u32>,
+    max_depth: Option<u32>,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+    mtime_cache: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> String {
+    let abs_path = abs_path_for(path, cwd);
+
+    let ctx = match configured_context(
+        &abs_path,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cache_dir,
+        no_cache,
+        mtime_cache,
+        include,
+        exclude,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => return e,
+    };
+
+    analyze_with(
+        &ctx.analyzer,
+        path,
+        focus,
+        ctx.follow_depth,
+        ctx.max_depth,
+        ctx.ast_recursion_limit,
+        cwd,
+        ctx.traverser,
+    )
+}
+
+/// Same as [`analyze`], but restricts traversal to files matching at least one of `include`
+/// glob patterns (e.g. `src/**/*.rs`) while pruning `exclude` patterns and nested
+/// `.gitignore`/`.ignore` rules inline as directories are visited, instead of collecting every
+/// file under `path` and filtering the result afterward.
+pub fn analyze_filtered(
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: u32,
+    max_depth: u32,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> String {
+    let traverser = FileTraverser::new().with_include(include).with_exclude(exclude);
+    analyze_with(
+        get_analyzer(),
+        path,
+        focus,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cwd,
+        traverser,
+    )
+}
+
+/// Same as [`analyze`], but reports [`ProgressData`] updates via `progress` as files are
+/// enumerated and then parsed, and checks `stop_flag` between files so a caller on another
+/// thread can cancel a run in progress.
+pub fn analyze_with_progress(
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: u32,
+    max_depth: u32,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    progress: ProgressCallback,
+    stop_flag: Option<Arc<AtomicBool>>,
+) -> String {
+    let mut analyzer = CodeAnalyzer::new().with_progress(progress.clone());
+    let mut traverser = FileTraverser::new().with_progress(progress);
+    if let Some(stop_flag) = stop_flag {
+        analyzer = analyzer.with_stop_flag(stop_flag.clone());
+        traverser = traverser.with_stop_flag(stop_flag);
+    }
+
+    analyze_with(
+        &analyzer,
+        path,
+        focus,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cwd,
+        traverser,
+    )
+}
+
+/// Same as [`analyze`], but renders the result as a stable JSON schema (full call graph,
+/// per-file [`AnalysisResult`]s, and focused call chains as node/edge lists) instead of
+/// the human-formatted text, for editor integrations and other tooling.
+///
+/// Like [`analyze_configured`], honors a discovered [`config::CONFIG_FILE_NAME`] and the
+/// on-disk cache for whichever of `follow_depth`/`max_depth`/`ast_recursion_limit`/`include`/
+/// `exclude`/`mtime_cache` the caller leaves unset.
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_json(
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: Option<u32>,
+    max_depth: Option<u32>,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+    mtime_cache: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> String {
+    let abs_path = abs_path_for(path, cwd);
+    let cwd_path = PathBuf::from(cwd);
+
+    let to_json = |output: JsonOutput| {
+        serde_json::to_string_pretty(&output)
+            .unwrap_or_else(|e| format!("{{\"mode\":\"error\",\"message\":\"{}\"}}", e))
+    };
+
+    let ctx = match configured_context(
+        &abs_path,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cache_dir,
+        no_cache,
+        mtime_cache,
+        include,
+        exclude,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => return to_json(JsonOutput::Error { message: e }),
+    };
+
+    if let Err(e) = ctx.traverser.validate_path(&abs_path) {
+        return to_json(JsonOutput::Error { message: e });
+    }
+
+    let focus_owned = focus.map(|s| s.to_string());
+    let mode = ctx.analyzer.determine_mode(&focus_owned, &abs_path);
+    let rel_path =
+        |p: &Path| p.strip_prefix(&cwd_path).unwrap_or(p).to_string_lossy().to_string();
+
+    let output = match mode {
+        AnalysisMode::Focused => {
+            let focus = focus.unwrap_or("");
+            let files_to_analyze = if abs_path.is_file() {
+                vec![abs_path.clone()]
+            } else {
+                match ctx.traverser.collect_files_for_focused(&abs_path, ctx.max_depth) {
+                    Ok(files) => files,
+                    Err(e) => return to_json(JsonOutput::Error { message: e }),
+                }
+            };
+
+            use rayon::prelude::*;
+            let all_results: Result<Vec<_>, String> = files_to_analyze
+                .par_iter()
+                .map(|file_path| {
+                    ctx.analyzer
+                        .analyze_file(file_path, &AnalysisMode::Semantic, ctx.ast_recursion_limit)
+                        .map(|result| (file_path.clone(), result))
+                })
+                .collect();
+
+            match all_results {
+                Ok(all_results) => {
+                    let graph = CallGraph::build_from_results(&all_results);
+                    let incoming_chains = if ctx.follow_depth > 0 {
+                        graph.find_incoming_chains(focus, ctx.follow_depth)
+                    } else {
+                        vec![]
+                    };
+                    let outgoing_chains = if ctx.follow_depth > 0 {
+                        graph.find_outgoing_chains(focus, ctx.follow_depth)
+                    } else {
+                        vec![]
+                    };
+                    let definitions = graph.definitions_for(focus);
+                    let suggestions = if definitions.is_empty() {
+                        graph.suggest(focus)
+                    } else {
+                        vec![]
+                    };
+
+                    JsonOutput::Focused {
+                        focus: JsonFocusedAnalysis::new(
+                            focus,
+                            ctx.follow_depth,
+                            &files_to_analyze,
+                            &definitions,
+                            &incoming_chains,
+                            &outgoing_chains,
+                            &suggestions,
+                            &cwd_path,
+                        ),
+                        graph: JsonCallGraph::from_graph(&graph, &cwd_path),
+                    }
+                }
+                Err(e) => JsonOutput::Error { message: e },
+            }
+        }
+        AnalysisMode::Semantic | AnalysisMode::Structure if abs_path.is_file() => {
+            match ctx.analyzer.analyze_file(&abs_path, &mode, ctx.ast_recursion_limit) {
+                Ok(result) => JsonOutput::File {
+                    path: rel_path(&abs_path),
+                    result,
+                },
+                Err(e) => JsonOutput::Error { message: e },
+            }
+        }
+        AnalysisMode::Semantic | AnalysisMode::Structure => {
+            match ctx.analyzer.analyze_directory_raw(
+                &abs_path,
+                ctx.max_depth,
+                ctx.ast_recursion_limit,
+                &ctx.traverser,
+                &mode,
+            ) {
+                Ok((results, _skipped)) => {
+                    let project = ProjectModel::discover(&abs_path, &results);
+                    JsonOutput::Directory {
+                        path: rel_path(&abs_path),
+                        files: directory_results_to_json(&results, &cwd_path),
+                        packages: packages_to_json(&project, &cwd_path),
+                    }
+                }
+                Err(e) => JsonOutput::Error { message: e },
+            }
+        }
+    };
+
+    to_json(output)
+}
+
+/// Report every defined function/class/method in `path` that has no incoming caller or
+/// type-reference edge and is not a conventional entry point (`main`, test functions). Bare
+/// grep can't produce this list because it has no notion of call or type-reference edges.
+///
+/// Like [`analyze_configured`], honors a discovered [`config::CONFIG_FILE_NAME`] and the
+/// on-disk cache for whichever of `max_depth`/`ast_recursion_limit`/`include`/`exclude`/
+/// `mtime_cache` the caller leaves unset.
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_unreferenced(
+    path: &str,
+    max_depth: Option<u32>,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+    mtime_cache: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> String {
+    let abs_path = abs_path_for(path, cwd);
+    let cwd_path = PathBuf::from(cwd);
+
+    let ctx = match configured_context(
+        &abs_path,
+        None,
+        max_depth,
+        ast_recursion_limit,
+        cache_dir,
+        no_cache,
+        mtime_cache,
+        include,
+        exclude,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = ctx.traverser.validate_path(&abs_path) {
+        return e;
+    }
+
+    let files_to_analyze = if abs_path.is_file() {
+        vec![abs_path.clone()]
+    } else {
+        match ctx.traverser.collect_files_for_focused(&abs_path, ctx.max_depth) {
+            Ok(files) => files,
+            Err(e) => return format!("Analysis error: {}", e),
+        }
+    };
+
+    use rayon::prelude::*;
+    let all_results: Result<Vec<_>, String> = files_to_analyze
+        .par_iter()
+        .map(|file_path| {
+            ctx.analyzer
+                .analyze_file(file_path, &AnalysisMode::Semantic, ctx.ast_recursion_limit)
+                .map(|result| (file_path.clone(), result))
+        })
+        .collect();
+
+    let all_results = match all_results {
+        Ok(results) => results,
+        Err(e) => return format!("Analysis error: {}", e),
+    };
+
+    let graph = CallGraph::build_from_results(&all_results);
+    let unreferenced = graph.unreferenced_definitions();
+
+    if unreferenced.is_empty() {
+        return "No unreferenced definitions found.\n".to_string();
+    }
+
+    let mut output = format!("Unreferenced definitions ({}):\n\n", unreferenced.len());
+    for (name, file, line) in unreferenced {
+        let rel = file.strip_prefix(&cwd_path).unwrap_or(file).to_string_lossy();
+        output.push_str(&format!("  {} ({}:{})\n", name, rel, line));
+    }
+    output
+}
+
+/// Same as the focused branch of [`analyze`], but appends a `SOURCE CONTEXT:` section with an
+/// `annotate-snippets`-style rendered excerpt (aligned line-number gutter, `context_lines` lines
+/// of surrounding source, and a `^^^^` underline under the symbol) for each definition, instead
+/// of leaving callers to resolve bare `file:line` references themselves.
+///
+/// Like [`analyze_configured`], honors a discovered [`config::CONFIG_FILE_NAME`] and the
+/// on-disk cache for whichever of `follow_depth`/`max_depth`/`ast_recursion_limit`/`include`/
+/// `exclude`/`mtime_cache` the caller leaves unset.
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_focused_snippets(
+    path: &str,
+    focus: &str,
+    follow_depth: Option<u32>,
+    max_depth: Option<u32>,
+    context_lines: usize,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    cache_dir: Option<&str>,
+    no_cache: bool,
+    mtime_cache: bool,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> String {
+    let abs_path = abs_path_for(path, cwd);
+
+    let ctx = match configured_context(
+        &abs_path,
+        follow_depth,
+        max_depth,
+        ast_recursion_limit,
+        cache_dir,
+        no_cache,
+        mtime_cache,
+        include,
+        exclude,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => return e,
+    };
+
+    if let Err(e) = ctx.traverser.validate_path(&abs_path) {
+        return e;
+    }
+
+    let (mut output, definitions) = match ctx.analyzer.analyze_focused(
+        &abs_path,
+        focus,
+        ctx.follow_depth,
+        ctx.max_depth,
+        ctx.ast_recursion_limit,
+        &ctx.traverser,
+    ) {
+        Ok(result) => result,
+        Err(e) => return format!("Analysis error: {}", e),
+    };
+
+    if !definitions.is_empty() {
+        output.push_str("\nSOURCE CONTEXT:\n\n");
+        for (file, line) in &definitions {
+            let Some(rendered) = snippet::render(file, *line, focus, context_lines) else {
+                continue;
+            };
+            output.push_str(&format!("{}:{}\n", file.display(), line));
+            output.push_str(&rendered);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+fn analyze_with(
+    analyzer: &CodeAnalyzer,
+    path: &str,
+    focus: Option<&str>,
+    follow_depth: u32,
+    max_depth: u32,
+    ast_recursion_limit: Option<usize>,
+    cwd: &str,
+    traverser: FileTraverser,
+) -> String {
+    let abs_path = if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        PathBuf::from(cwd).join(path)
+    };
 
     if let Err(e) = traverser.validate_path(&abs_path) {
         return e;
@@ -255,7 +971,7 @@ pub fn analyze(
                 ast_recursion_limit,
                 &traverser,
             ) {
-                Ok(output) => output,
+                Ok((output, _)) => output,
                 Err(e) => return format!("Analysis error: {}", e),
             }
         }
@@ -0,0 +1,75 @@
+// Copyright 2024 Block, Inc. (original code from https://github.com/block/goose)
+// Copyright 2025 utapyngo (modifications)
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::analyze::lock_or_recover;
+
+/// Minimum gap between emitted [`ProgressData`] updates, so a fast parallel pass over thousands
+/// of small files doesn't flood the sink with one callback per file.
+const EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A point-in-time snapshot of an in-progress analysis, suitable for driving a progress bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressData {
+    /// 1 = enumerating files, 2 = parsing/extracting
+    pub current_stage: u8,
+    pub max_stage: u8,
+    pub entries_to_check: usize,
+    pub entries_checked: usize,
+}
+
+/// A sink that receives [`ProgressData`] updates. Shared across parallel workers, so it must be
+/// `Send + Sync`.
+pub type ProgressCallback = Arc<dyn Fn(ProgressData) + Send + Sync>;
+
+/// Throttles a [`ProgressCallback`] to roughly one emission per [`EMIT_INTERVAL`].
+#[derive(Clone)]
+pub struct ProgressReporter {
+    callback: Option<ProgressCallback>,
+    last_emit: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ProgressReporter {
+    pub fn new(callback: Option<ProgressCallback>) -> Self {
+        Self {
+            callback,
+            last_emit: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Emit unconditionally, bypassing the throttle. Used for stage transitions, which are rare
+    /// enough on their own that they should never be dropped.
+    pub fn emit(&self, data: ProgressData) {
+        if let Some(callback) = &self.callback {
+            callback(data);
+        }
+    }
+
+    /// Emit if at least [`EMIT_INTERVAL`] has passed since the last throttled emission; an
+    /// unthrottled [`Self::emit`] does not reset the interval.
+    pub fn emit_throttled(&self, data: ProgressData) {
+        if self.callback.is_none() {
+            return;
+        }
+
+        let mut last = lock_or_recover(&self.last_emit, |guard| *guard = None);
+        let now = Instant::now();
+        let should_emit = last.is_none_or(|previous| now.duration_since(previous) >= EMIT_INTERVAL);
+        if !should_emit {
+            return;
+        }
+        *last = Some(now);
+        drop(last);
+
+        self.emit(data);
+    }
+}
+
+impl Default for ProgressReporter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
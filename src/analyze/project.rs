@@ -0,0 +1,468 @@
+// Copyright 2024 Block, Inc. (original code from https://github.com/block/goose)
+// Copyright 2025 utapyngo (modifications)
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::types::EntryType;
+
+/// Build manifests recognized as package/crate boundaries
+const MANIFEST_FILES: &[&str] = &["Cargo.toml", "package.json", "go.mod", "pyproject.toml"];
+
+/// A discovered package/crate: the manifest that declared it, the source subtree it owns,
+/// and aggregate stats rolled up from the files under that subtree
+#[derive(Debug, Clone)]
+pub struct PackageInfo {
+    pub name: String,
+    pub manifest: PathBuf,
+    pub root: PathBuf,
+    pub dependencies: Vec<String>,
+    pub line_count: usize,
+    pub function_count: usize,
+}
+
+/// Build-manifest-aware view of a directory tree, inspired by rust-analyzer's
+/// `ra_project_model`: recognizes package manifests, resolves Cargo workspace members, and
+/// groups per-file analysis results by the package that owns them.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectModel {
+    pub packages: Vec<PackageInfo>,
+}
+
+impl ProjectModel {
+    /// Discover every package under `root` and roll up LOC/function counts from `results`
+    pub fn discover(root: &Path, results: &[(PathBuf, EntryType)]) -> Self {
+        let manifest_paths = Self::find_manifests(root);
+        let workspace_members = Self::workspace_member_roots(root);
+
+        let mut packages: Vec<PackageInfo> = manifest_paths
+            .into_iter()
+            .filter(|manifest| Self::is_workspace_member(manifest, root, &workspace_members))
+            .filter_map(|manifest| Self::package_from_manifest(&manifest))
+            .collect();
+
+        for (file, EntryType::File(result)) in results {
+            let owner = packages
+                .iter()
+                .enumerate()
+                .filter(|(_, pkg)| file.starts_with(&pkg.root))
+                .max_by_key(|(_, pkg)| pkg.root.as_os_str().len())
+                .map(|(i, _)| i);
+
+            if let Some(i) = owner {
+                packages[i].line_count += result.line_count;
+                packages[i].function_count += result.function_count;
+            }
+        }
+
+        packages.sort_by(|a, b| a.root.cmp(&b.root));
+        Self { packages }
+    }
+
+    /// Render a `PACKAGES:` report section, with package roots shown relative to `root`
+    pub fn format_packages_section(&self, root: &Path) -> String {
+        if self.packages.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::from("\nPACKAGES:\n");
+        for pkg in &self.packages {
+            let rel_root = pkg.root.strip_prefix(root).unwrap_or(&pkg.root);
+            let rel_root = if rel_root.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                rel_root.to_string_lossy().to_string()
+            };
+
+            output.push_str(&format!(
+                "  {} ({})\n    LOC: {}, functions: {}, dependencies: {}\n",
+                pkg.name,
+                rel_root,
+                pkg.line_count,
+                pkg.function_count,
+                pkg.dependencies.len(),
+            ));
+        }
+        output
+    }
+
+    /// Whether a Cargo.toml belongs to the set of declared workspace members (always true
+    /// for non-Cargo manifests, and for the workspace root's own `[package]` if any)
+    fn is_workspace_member(
+        manifest: &Path,
+        root: &Path,
+        workspace_members: &Option<HashSet<PathBuf>>,
+    ) -> bool {
+        let is_cargo_toml = manifest.file_name().and_then(|n| n.to_str()) == Some("Cargo.toml");
+        let Some(members) = workspace_members else {
+            return true;
+        };
+        if !is_cargo_toml {
+            return true;
+        }
+
+        let pkg_root = manifest.parent().unwrap_or(root);
+        pkg_root == root || members.contains(pkg_root)
+    }
+
+    /// If `root` has a `[workspace]` Cargo.toml, resolve its `members` globs (supporting a
+    /// trailing `/*` wildcard) to concrete member directories
+    fn workspace_member_roots(root: &Path) -> Option<HashSet<PathBuf>> {
+        let content = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+        if !content.contains("[workspace]") {
+            return None;
+        }
+
+        let mut roots = HashSet::new();
+        for member in Self::workspace_members(&content).unwrap_or_default() {
+            if let Some(prefix) = member.strip_suffix("/*") {
+                let base = root.join(prefix);
+                let Ok(entries) = fs::read_dir(&base) else {
+                    continue;
+                };
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    if path.is_dir() && path.join("Cargo.toml").is_file() {
+                        roots.insert(path);
+                    }
+                }
+            } else {
+                roots.insert(root.join(member));
+            }
+        }
+        Some(roots)
+    }
+
+    fn workspace_members(content: &str) -> Option<Vec<String>> {
+        let body = Self::toml_section_body(content, "workspace")?;
+        let members_start = body.find("members")?;
+        let rest = &body[members_start..];
+        let bracket_start = rest.find('[')?;
+        let bracket_end = rest[bracket_start..].find(']')? + bracket_start;
+        let inner = &rest[bracket_start + 1..bracket_end];
+
+        Some(
+            inner
+                .split(',')
+                .map(|s| s.trim().trim_matches('"').to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        )
+    }
+
+    fn find_manifests(root: &Path) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        Self::find_manifests_recursive(root, &mut found);
+        found
+    }
+
+    fn find_manifests_recursive(dir: &Path, found: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            if path.is_file() && MANIFEST_FILES.contains(&name) {
+                found.push(path.clone());
+            } else if path.is_dir()
+                && !name.starts_with('.')
+                && name != "node_modules"
+                && name != "target"
+                && name != "__pycache__"
+                && name != "vendor"
+            {
+                Self::find_manifests_recursive(&path, found);
+            }
+        }
+    }
+
+    fn package_from_manifest(manifest: &Path) -> Option<PackageInfo> {
+        let root = manifest.parent()?.to_path_buf();
+        let name = manifest.file_name().and_then(|n| n.to_str())?;
+
+        let (package_name, dependencies) = match name {
+            "Cargo.toml" => Self::parse_cargo_toml(manifest)?,
+            "package.json" => Self::parse_package_json(manifest)?,
+            "go.mod" => Self::parse_go_mod(manifest)?,
+            "pyproject.toml" => Self::parse_pyproject_toml(manifest)?,
+            _ => return None,
+        };
+
+        Some(PackageInfo {
+            name: package_name,
+            manifest: manifest.to_path_buf(),
+            root,
+            dependencies,
+            line_count: 0,
+            function_count: 0,
+        })
+    }
+
+    /// `None` for a pure virtual workspace manifest (`[workspace]` with no `[package]`)
+    fn parse_cargo_toml(manifest: &Path) -> Option<(String, Vec<String>)> {
+        let content = fs::read_to_string(manifest).ok()?;
+        let name = Self::toml_value(&content, "package", "name")?;
+        let dependencies = Self::toml_section_keys(&content, "dependencies");
+        Some((name, dependencies))
+    }
+
+    fn parse_package_json(manifest: &Path) -> Option<(String, Vec<String>)> {
+        let content = fs::read_to_string(manifest).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let name = value.get("name")?.as_str()?.to_string();
+
+        let mut dependencies = Vec::new();
+        for section in ["dependencies", "devDependencies"] {
+            if let Some(deps) = value.get(section).and_then(|v| v.as_object()) {
+                dependencies.extend(deps.keys().cloned());
+            }
+        }
+
+        Some((name, dependencies))
+    }
+
+    fn parse_go_mod(manifest: &Path) -> Option<(String, Vec<String>)> {
+        let content = fs::read_to_string(manifest).ok()?;
+        let name = content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("module "))
+            .map(|m| m.trim().to_string())?;
+
+        let mut dependencies = Vec::new();
+        let mut in_require_block = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with("require (") {
+                in_require_block = true;
+                continue;
+            }
+            if in_require_block {
+                if line == ")" {
+                    in_require_block = false;
+                } else if let Some(dep) = line.split_whitespace().next() {
+                    dependencies.push(dep.to_string());
+                }
+            } else if let Some(rest) = line.strip_prefix("require ") {
+                if let Some(dep) = rest.split_whitespace().next() {
+                    dependencies.push(dep.to_string());
+                }
+            }
+        }
+
+        Some((name, dependencies))
+    }
+
+    fn parse_pyproject_toml(manifest: &Path) -> Option<(String, Vec<String>)> {
+        let content = fs::read_to_string(manifest).ok()?;
+        let name = Self::toml_value(&content, "tool.poetry", "name")
+            .or_else(|| Self::toml_value(&content, "project", "name"))?;
+
+        let dependencies = Self::toml_section_keys(&content, "tool.poetry.dependencies")
+            .into_iter()
+            .filter(|key| key != "python")
+            .collect();
+
+        Some((name, dependencies))
+    }
+
+    /// Find `key = "value"` inside a `[section]` block of a TOML-like file
+    fn toml_value(content: &str, section: &str, key: &str) -> Option<String> {
+        let body = Self::toml_section_body(content, section)?;
+        for line in body.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let (Some(k), Some(v)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if k.trim() == key {
+                return Some(v.trim().trim_matches('"').to_string());
+            }
+        }
+        None
+    }
+
+    /// Collect the top-level keys declared inside a `[section]` block (e.g. dependency names)
+    fn toml_section_keys(content: &str, section: &str) -> Vec<String> {
+        let Some(body) = Self::toml_section_body(content, section) else {
+            return vec![];
+        };
+
+        body.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split('=').next())
+            .map(|key| key.trim().trim_matches('"').to_string())
+            .collect()
+    }
+
+    /// Extract the raw lines between a `[section]` header and the next `[...]` header
+    fn toml_section_body<'a>(content: &'a str, section: &str) -> Option<&'a str> {
+        let header = format!("[{}]", section);
+        let start = content.find(&header)? + header.len();
+        let rest = &content[start..];
+        let end = rest
+            .match_indices('\n')
+            .map(|(i, _)| i + 1)
+            .find(|&i| rest[i..].trim_start().starts_with('['))
+            .unwrap_or(rest.len());
+        Some(&rest[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::types::AnalysisResult;
+
+    #[test]
+    fn discovers_single_cargo_package() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"my-crate\"\nversion = \"0.1.0\"\n\n\
+             [dependencies]\nserde = \"1\"\nlru = { version = \"0.12\" }\n",
+        )
+        .unwrap();
+
+        let results = vec![(
+            dir.path().join("src/lib.rs"),
+            EntryType::File(AnalysisResult {
+                line_count: 42,
+                function_count: 3,
+                ..AnalysisResult::empty(42)
+            }),
+        )];
+
+        let model = ProjectModel::discover(dir.path(), &results);
+        assert_eq!(model.packages.len(), 1);
+        let pkg = &model.packages[0];
+        assert_eq!(pkg.name, "my-crate");
+        assert_eq!(pkg.line_count, 42);
+        assert_eq!(pkg.function_count, 3);
+        assert_eq!(pkg.dependencies.len(), 2);
+        assert!(pkg.dependencies.contains(&"serde".to_string()));
+    }
+
+    #[test]
+    fn workspace_root_is_excluded_and_members_are_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+
+        fs::create_dir_all(dir.path().join("crates/foo")).unwrap();
+        fs::write(
+            dir.path().join("crates/foo/Cargo.toml"),
+            "[package]\nname = \"foo\"\n",
+        )
+        .unwrap();
+
+        fs::create_dir_all(dir.path().join("crates/bar")).unwrap();
+        fs::write(
+            dir.path().join("crates/bar/Cargo.toml"),
+            "[package]\nname = \"bar\"\n",
+        )
+        .unwrap();
+
+        // A nested crate that exists but isn't a declared member should be excluded.
+        fs::create_dir_all(dir.path().join("scratch")).unwrap();
+        fs::write(
+            dir.path().join("scratch/Cargo.toml"),
+            "[package]\nname = \"scratch\"\n",
+        )
+        .unwrap();
+
+        let model = ProjectModel::discover(dir.path(), &[]);
+        let names: Vec<&str> = model.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["bar", "foo"]);
+    }
+
+    #[test]
+    fn discovers_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "my-app", "dependencies": {"react": "^18.0.0"}}"#,
+        )
+        .unwrap();
+
+        let model = ProjectModel::discover(dir.path(), &[]);
+        assert_eq!(model.packages.len(), 1);
+        assert_eq!(model.packages[0].name, "my-app");
+        assert_eq!(model.packages[0].dependencies, vec!["react".to_string()]);
+    }
+
+    #[test]
+    fn discovers_go_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("go.mod"),
+            "module github.com/example/thing\n\ngo 1.21\n\n\
+             require (\n\tgithub.com/pkg/errors v0.9.1\n)\n",
+        )
+        .unwrap();
+
+        let model = ProjectModel::discover(dir.path(), &[]);
+        assert_eq!(model.packages.len(), 1);
+        assert_eq!(model.packages[0].name, "github.com/example/thing");
+        assert_eq!(
+            model.packages[0].dependencies,
+            vec!["github.com/pkg/errors".to_string()]
+        );
+    }
+
+    #[test]
+    fn discovers_pyproject_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.poetry]\nname = \"my-lib\"\n\n\
+             [tool.poetry.dependencies]\npython = \"^3.11\"\nrequests = \"^2.0\"\n",
+        )
+        .unwrap();
+
+        let model = ProjectModel::discover(dir.path(), &[]);
+        assert_eq!(model.packages.len(), 1);
+        assert_eq!(model.packages[0].name, "my-lib");
+        assert_eq!(model.packages[0].dependencies, vec!["requests".to_string()]);
+    }
+
+    #[test]
+    fn format_packages_section_is_empty_without_packages() {
+        let model = ProjectModel::default();
+        assert_eq!(model.format_packages_section(Path::new("/repo")), "");
+    }
+
+    #[test]
+    fn format_packages_section_lists_each_package() {
+        let model = ProjectModel {
+            packages: vec![PackageInfo {
+                name: "my-crate".to_string(),
+                manifest: PathBuf::from("/repo/Cargo.toml"),
+                root: PathBuf::from("/repo"),
+                dependencies: vec!["serde".to_string()],
+                line_count: 10,
+                function_count: 2,
+            }],
+        };
+
+        let output = model.format_packages_section(Path::new("/repo"));
+        assert!(output.contains("PACKAGES:"));
+        assert!(output.contains("my-crate"));
+        assert!(output.contains("LOC: 10"));
+        assert!(output.contains("functions: 2"));
+        assert!(output.contains("dependencies: 1"));
+    }
+}
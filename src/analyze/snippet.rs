@@ -0,0 +1,94 @@
+// Copyright 2024 Block, Inc. (original code from https://github.com/block/goose)
+// Copyright 2025 utapyngo (modifications)
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::Path;
+
+/// Render an `annotate-snippets`-style excerpt around `line_1based` in `path`: a gutter of
+/// aligned line numbers, `context` lines of surrounding source above and below, and a `^^^^`
+/// underline beneath the first occurrence of `symbol` on that line.
+///
+/// Returns `None` if the file can't be read or `line_1based` is out of range, so a caller can
+/// skip a definition rather than render a broken excerpt.
+pub fn render(path: &Path, line_1based: usize, symbol: &str, context: usize) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    if line_1based == 0 || line_1based > lines.len() {
+        return None;
+    }
+
+    let index = line_1based - 1;
+    let start = index.saturating_sub(context);
+    let end = (index + context).min(lines.len() - 1);
+    let gutter_width = (end + 1).to_string().len();
+
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+        out.push_str(&format!("{:>gutter_width$} | {}\n", i + 1, line));
+
+        if i == index
+            && let Some(col) = line.find(symbol)
+        {
+            out.push_str(&format!(
+                "{:gutter_width$} | {}{} {}\n",
+                "",
+                " ".repeat(col),
+                "^".repeat(symbol.chars().count()),
+                symbol
+            ));
+        }
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_fixture(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.rs");
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn renders_gutter_and_underline_around_target_line() {
+        let (_dir, path) =
+            write_fixture("fn a() {}\nfn helper() {\n    1\n}\nfn b() {}\nfn c() {}\n");
+
+        let rendered = render(&path, 2, "helper", 1).unwrap();
+        assert!(rendered.contains("1 | fn a() {}"));
+        assert!(rendered.contains("2 | fn helper() {"));
+        assert!(rendered.contains("3 |     1"));
+        assert!(rendered.contains("^^^^^^ helper"));
+        assert!(!rendered.contains("4 | }"));
+    }
+
+    #[test]
+    fn clamps_context_to_file_bounds() {
+        let (_dir, path) = write_fixture("fn only() {}\n");
+
+        let rendered = render(&path, 1, "only", 5).unwrap();
+        assert!(rendered.contains("1 | fn only() {}"));
+        assert!(rendered.contains("^^^^ only"));
+    }
+
+    #[test]
+    fn returns_none_for_out_of_range_line() {
+        let (_dir, path) = write_fixture("fn a() {}\n");
+        assert!(render(&path, 99, "a", 1).is_none());
+    }
+
+    #[test]
+    fn omits_underline_when_symbol_not_found_on_line() {
+        let (_dir, path) = write_fixture("fn a() {}\n");
+        let rendered = render(&path, 1, "nowhere", 0).unwrap();
+        assert!(rendered.contains("1 | fn a() {}"));
+        assert!(!rendered.contains('^'));
+    }
+}
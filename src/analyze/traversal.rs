@@ -4,22 +4,253 @@
 
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
+use super::progress::{ProgressCallback, ProgressData, ProgressReporter};
 use super::types::{AnalysisResult, EntryType};
 use crate::lang;
 
-/// Handles file system traversal for analysis
-pub struct FileTraverser;
+/// Directory/file names pruned unconditionally, regardless of `include`/`exclude` patterns
+const DEFAULT_EXCLUDED_NAMES: &[&str] = &["node_modules", "target", "__pycache__", "vendor"];
 
-impl Default for FileTraverser {
-    fn default() -> Self {
-        Self
+/// Chained symlink resolutions allowed before a target is treated as an unresolvable cycle
+const MAX_SYMLINK_JUMPS: usize = 20;
+
+/// Why a directory entry was skipped rather than descended into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalError {
+    /// A symlink chain looped back on itself, pointed into an already-visited ancestor, or
+    /// exceeded [`MAX_SYMLINK_JUMPS`]
+    InfiniteRecursion,
+    /// A symlink's target does not exist
+    NonExistentFile,
+}
+
+/// A directory entry that traversal skipped instead of descending into
+#[derive(Debug, Clone)]
+pub struct SkippedEntry {
+    pub path: PathBuf,
+    pub error: TraversalError,
+}
+
+/// Follow a chain of symlinks (relative targets resolved against their link's parent directory)
+/// up to [`MAX_SYMLINK_JUMPS`] hops, returning the first non-symlink path reached.
+fn resolve_symlink_chain(path: &Path) -> Result<PathBuf, TraversalError> {
+    let mut current = path.to_path_buf();
+    for _ in 0..MAX_SYMLINK_JUMPS {
+        let metadata =
+            std::fs::symlink_metadata(&current).map_err(|_| TraversalError::NonExistentFile)?;
+        if !metadata.file_type().is_symlink() {
+            return Ok(current);
+        }
+
+        let target = std::fs::read_link(&current).map_err(|_| TraversalError::NonExistentFile)?;
+        current = if target.is_absolute() {
+            target
+        } else {
+            current
+                .parent()
+                .map(|parent| parent.join(&target))
+                .unwrap_or(target)
+        };
+    }
+    Err(TraversalError::InfiniteRecursion)
+}
+
+/// A `.gitignore`-style glob: `/`-separated segments where `*` matches any run of characters
+/// within a segment, `?` matches a single character, and a literal `**` segment matches zero
+/// or more path segments.
+#[derive(Debug, Clone)]
+struct GlobPattern {
+    segments: Vec<String>,
+}
+
+impl GlobPattern {
+    fn new(pattern: &str) -> Self {
+        Self {
+            segments: pattern
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    fn matches(&self, rel_path: &str) -> bool {
+        let path_segments: Vec<&str> = rel_path.split('/').filter(|s| !s.is_empty()).collect();
+        let pattern_segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        Self::match_segments(&pattern_segments, &path_segments)
+    }
+
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (None, Some(_)) => false,
+            (Some(&"**"), _) => {
+                Self::match_segments(&pattern[1..], path)
+                    || (!path.is_empty() && Self::match_segments(pattern, &path[1..]))
+            }
+            (Some(p), Some(s)) if Self::segment_matches(p, s) => {
+                Self::match_segments(&pattern[1..], &path[1..])
+            }
+            _ => false,
+        }
+    }
+
+    fn segment_matches(pattern: &str, name: &str) -> bool {
+        fn helper(p: &[u8], n: &[u8]) -> bool {
+            match (p.first(), n.first()) {
+                (None, None) => true,
+                (Some(b'*'), _) => helper(&p[1..], n) || (!n.is_empty() && helper(p, &n[1..])),
+                (Some(b'?'), Some(_)) => helper(&p[1..], &n[1..]),
+                (Some(a), Some(b)) if a == b => helper(&p[1..], &n[1..]),
+                _ => false,
+            }
+        }
+        helper(pattern.as_bytes(), name.as_bytes())
+    }
+
+    /// The glob-free path prefix of this pattern, i.e. the deepest directory that's guaranteed
+    /// to contain every match, used to restrict traversal to only the subtree that could
+    /// possibly satisfy it instead of walking the whole tree and filtering afterward.
+    fn literal_prefix(&self) -> PathBuf {
+        let mut prefix = PathBuf::new();
+        for segment in &self.segments {
+            if segment.contains('*') || segment.contains('?') {
+                break;
+            }
+            prefix.push(segment);
+        }
+        prefix
+    }
+}
+
+/// One parsed `.gitignore`/`.ignore` line, rewritten to a root-relative [`GlobPattern`] so
+/// rules from every ancestor directory can be checked against a single relative path.
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: GlobPattern,
+    negate: bool,
+    dir_only: bool,
+}
+
+fn join_rel(base: &str, suffix: &str) -> String {
+    if base.is_empty() {
+        suffix.to_string()
+    } else {
+        format!("{}/{}", base, suffix)
+    }
+}
+
+/// Parse a `.gitignore`/`.ignore` file's contents found in the directory at root-relative path
+/// `dir_rel` into root-relative [`IgnoreRule`]s: a pattern containing an internal `/` is
+/// anchored to that directory, while a bare name (git's shorthand for "anywhere below here")
+/// is rewritten with a leading `**/` so it still matches at any depth under `dir_rel`.
+fn parse_ignore_file(content: &str, dir_rel: &str) -> Vec<IgnoreRule> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+
+            let negate = line.starts_with('!');
+            let line = if negate { &line[1..] } else { line };
+            let dir_only = line.ends_with('/');
+            let line = line.strip_suffix('/').unwrap_or(line);
+
+            let anchored = line.trim_start_matches('/').contains('/');
+            let line = line.trim_start_matches('/');
+
+            let rooted = if anchored {
+                join_rel(dir_rel, line)
+            } else {
+                join_rel(dir_rel, &format!("**/{}", line))
+            };
+
+            Some(IgnoreRule {
+                pattern: GlobPattern::new(&rooted),
+                negate,
+                dir_only,
+            })
+        })
+        .collect()
+}
+
+/// `path` relative to `root`, with `/` separators regardless of platform
+fn relative_str(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Handles file system traversal for analysis, honoring user-supplied include/exclude glob
+/// patterns and nested `.gitignore`/`.ignore` files discovered along the walk.
+#[derive(Clone, Default)]
+pub struct FileTraverser {
+    include: Vec<GlobPattern>,
+    exclude: Vec<GlobPattern>,
+    follow_symlinks: bool,
+    progress: Option<ProgressCallback>,
+    stop_flag: Option<Arc<AtomicBool>>,
+}
+
+impl std::fmt::Debug for FileTraverser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileTraverser")
+            .field("include", &self.include)
+            .field("exclude", &self.exclude)
+            .field("follow_symlinks", &self.follow_symlinks)
+            .field("progress", &self.progress.is_some())
+            .field("stop_flag", &self.stop_flag.is_some())
+            .finish()
     }
 }
 
 impl FileTraverser {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Restrict traversal to files matching at least one of these glob patterns (e.g.
+    /// `src/**/*.rs`). Each pattern's literal (glob-free) prefix is used to skip subtrees that
+    /// couldn't possibly contain a match, rather than collecting every file and filtering
+    /// afterward. An empty list (the default) includes everything.
+    pub fn with_include(mut self, patterns: Vec<String>) -> Self {
+        self.include = patterns.iter().map(|p| GlobPattern::new(p)).collect();
+        self
+    }
+
+    /// Prune files and directories matching any of these glob patterns, tested inline against
+    /// each directory entry as it's visited so an excluded directory is never even opened.
+    pub fn with_exclude(mut self, patterns: Vec<String>) -> Self {
+        self.exclude = patterns.iter().map(|p| GlobPattern::new(p)).collect();
+        self
+    }
+
+    /// Allow descending into symlinked directories that resolve outside the analyzed root.
+    /// By default (`false`), a symlink is only followed if its canonical target stays within
+    /// the root; either way, cycles and chains over [`MAX_SYMLINK_JUMPS`] are always rejected.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Receive [`ProgressData`] updates as [`Self::collect_directory_results`] enumerates and
+    /// then analyzes files, throttled to roughly one emission per 100ms.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Check `stop_flag` inside the parallel analysis loop in [`Self::collect_directory_results`]
+    /// so a consumer on another thread can abort a run in progress.
+    pub fn with_stop_flag(mut self, stop_flag: Arc<AtomicBool>) -> Self {
+        self.stop_flag = Some(stop_flag);
+        self
     }
 
     /// Validate that a path exists
@@ -36,64 +267,248 @@ impl FileTraverser {
         path: &Path,
         max_depth: u32,
     ) -> Result<Vec<PathBuf>, String> {
-        let files = self.collect_files_recursive(path, 0, max_depth)?;
-        Ok(files)
+        Ok(self.collect_files_with_skipped(path, max_depth)?.0)
+    }
+
+    /// Same as [`collect_files_for_focused`], but also returns the symlinked directories that
+    /// were skipped as cycles or dangling targets instead of descended into.
+    pub fn collect_files_with_skipped(
+        &self,
+        path: &Path,
+        max_depth: u32,
+    ) -> Result<(Vec<PathBuf>, Vec<SkippedEntry>), String> {
+        let root_canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        self.collect_files_recursive(
+            path,
+            path,
+            0,
+            max_depth,
+            &[],
+            &root_canonical,
+            std::slice::from_ref(&root_canonical),
+        )
+    }
+
+    fn is_included(&self, root: &Path, file_path: &Path) -> bool {
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| pattern.matches(&relative_str(root, file_path)))
     }
 
-    /// Recursively collect files
+    /// Whether `dir_path` lies on the path toward, or already inside, some include pattern's
+    /// literal prefix, i.e. whether descending further could still reach a match.
+    fn could_contain_include_match(&self, root: &Path, dir_path: &Path) -> bool {
+        if self.include.is_empty() {
+            return true;
+        }
+
+        let rel_dir = relative_str(root, dir_path);
+        let dir_segments: Vec<&str> = rel_dir.split('/').filter(|s| !s.is_empty()).collect();
+
+        self.include.iter().any(|pattern| {
+            let prefix = pattern.literal_prefix();
+            let prefix_segments: Vec<&str> = prefix
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect();
+            let shared = dir_segments.len().min(prefix_segments.len());
+            dir_segments[..shared] == prefix_segments[..shared]
+        })
+    }
+
+    fn matches_exclude(&self, rel_path: &str) -> bool {
+        self.exclude.iter().any(|pattern| pattern.matches(rel_path))
+    }
+
+    /// Load this directory's own `.gitignore`/`.ignore` rules (if present), rewritten relative
+    /// to `root` so they can be compared against entries anywhere below.
+    fn load_ignore_rules(&self, root: &Path, dir: &Path) -> Vec<IgnoreRule> {
+        let dir_rel = relative_str(root, dir);
+        [".gitignore", ".ignore"]
+            .iter()
+            .filter_map(|name| std::fs::read_to_string(dir.join(name)).ok())
+            .flat_map(|content| parse_ignore_file(&content, &dir_rel))
+            .collect()
+    }
+
+    /// Whether `rel_path` is ignored per the accumulated rule stack: rules are checked in
+    /// order (outermost `.gitignore` first), and the last matching rule wins, so a nested
+    /// `.gitignore` can override or re-include what an ancestor excluded.
+    fn is_ignored(&self, rules: &[IgnoreRule], rel_path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            if rule.pattern.matches(rel_path) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+
+    /// Resolve a symlink to its canonical target, classifying it as [`TraversalError`] if the
+    /// chain is dangling or exceeds [`MAX_SYMLINK_JUMPS`]. Cycle/escape checks (which only make
+    /// sense for a directory target) are the caller's responsibility.
+    fn resolve_symlink(&self, entry_path: &Path) -> Result<PathBuf, TraversalError> {
+        let resolved = resolve_symlink_chain(entry_path)?;
+        std::fs::canonicalize(&resolved).map_err(|_| TraversalError::NonExistentFile)
+    }
+
+    /// Recursively collect files, pruning directories via [`DEFAULT_EXCLUDED_NAMES`], `exclude`
+    /// patterns, and accumulated `.gitignore`/`.ignore` rules before ever reading them;
+    /// restricting descent to subtrees implied by `include` patterns' literal prefixes; and
+    /// guarding symlinked directories against cycles, dangling targets, and escapes from `root`.
     fn collect_files_recursive(
         &self,
+        root: &Path,
         path: &Path,
         current_depth: u32,
         max_depth: u32,
-    ) -> Result<Vec<PathBuf>, String> {
+        inherited_rules: &[IgnoreRule],
+        root_canonical: &Path,
+        visited: &[PathBuf],
+    ) -> Result<(Vec<PathBuf>, Vec<SkippedEntry>), String> {
         let mut files = Vec::new();
+        let mut skipped = Vec::new();
 
         if path.is_file() {
             let lang_id = lang::get_language_identifier(path);
             if !lang_id.is_empty() {
                 files.push(path.to_path_buf());
             }
-            return Ok(files);
+            return Ok((files, skipped));
         }
 
         // max_depth of 0 means unlimited depth
         if max_depth > 0 && current_depth >= max_depth {
-            return Ok(files);
+            return Ok((files, skipped));
         }
 
+        let mut rules = inherited_rules.to_vec();
+        rules.extend(self.load_ignore_rules(root, path));
+
         let entries = std::fs::read_dir(path)
             .map_err(|e| format!("Failed to read directory '{}': {}", path.display(), e))?;
 
         for entry in entries {
             let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-
             let entry_path = entry.path();
 
-            // Skip hidden directories and common non-source directories
-            if let Some(name) = entry_path.file_name().and_then(|n| n.to_str())
-                && (name.starts_with('.')
-                    || name == "node_modules"
-                    || name == "target"
-                    || name == "__pycache__"
-                    || name == "vendor")
-            {
+            let Some(name) = entry_path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with('.') || DEFAULT_EXCLUDED_NAMES.contains(&name) {
                 continue;
             }
 
-            if entry_path.is_file() {
-                let lang_id = lang::get_language_identifier(&entry_path);
-                if !lang_id.is_empty() {
-                    files.push(entry_path);
+            let is_symlink = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+
+            // A symlink's own is_dir()/is_file() follows the link, which mis-stats a dangling
+            // target as neither; resolve the chain up front so cycles/dangling links get their
+            // own classification instead of silently vanishing from the walk.
+            if is_symlink {
+                let canonical = match self.resolve_symlink(&entry_path) {
+                    Ok(canonical) => canonical,
+                    Err(error) => {
+                        skipped.push(SkippedEntry {
+                            path: entry_path,
+                            error,
+                        });
+                        continue;
+                    }
+                };
+
+                if !canonical.is_dir() {
+                    let rel = relative_str(root, &entry_path);
+                    if !self.matches_exclude(&rel)
+                        && !self.is_ignored(&rules, &rel, false)
+                        && self.is_included(root, &entry_path)
+                    {
+                        let lang_id = lang::get_language_identifier(&entry_path);
+                        if !lang_id.is_empty() {
+                            files.push(entry_path);
+                        }
+                    }
+                    continue;
+                }
+
+                if visited.contains(&canonical) {
+                    skipped.push(SkippedEntry {
+                        path: entry_path,
+                        error: TraversalError::InfiniteRecursion,
+                    });
+                    continue;
                 }
-            } else if entry_path.is_dir() {
-                let mut sub_files =
-                    self.collect_files_recursive(&entry_path, current_depth + 1, max_depth)?;
+                if !self.follow_symlinks && !canonical.starts_with(root_canonical) {
+                    continue;
+                }
+
+                let rel = relative_str(root, &entry_path);
+                if self.matches_exclude(&rel) || self.is_ignored(&rules, &rel, true) {
+                    continue;
+                }
+                if !self.could_contain_include_match(root, &entry_path) {
+                    continue;
+                }
+
+                let mut child_visited = visited.to_vec();
+                child_visited.push(canonical);
+                let (mut sub_files, mut sub_skipped) = self.collect_files_recursive(
+                    root,
+                    &entry_path,
+                    current_depth + 1,
+                    max_depth,
+                    &rules,
+                    root_canonical,
+                    &child_visited,
+                )?;
                 files.append(&mut sub_files);
+                skipped.append(&mut sub_skipped);
+                continue;
             }
+
+            let rel = relative_str(root, &entry_path);
+            let is_dir = entry_path.is_dir();
+            if self.matches_exclude(&rel) || self.is_ignored(&rules, &rel, is_dir) {
+                continue;
+            }
+
+            if !is_dir {
+                if self.is_included(root, &entry_path) {
+                    let lang_id = lang::get_language_identifier(&entry_path);
+                    if !lang_id.is_empty() {
+                        files.push(entry_path);
+                    }
+                }
+                continue;
+            }
+
+            if !self.could_contain_include_match(root, &entry_path) {
+                continue;
+            }
+
+            let mut child_visited = visited.to_vec();
+            if let Ok(canonical) = std::fs::canonicalize(&entry_path) {
+                child_visited.push(canonical);
+            }
+
+            let (mut sub_files, mut sub_skipped) = self.collect_files_recursive(
+                root,
+                &entry_path,
+                current_depth + 1,
+                max_depth,
+                &rules,
+                root_canonical,
+                &child_visited,
+            )?;
+            files.append(&mut sub_files);
+            skipped.append(&mut sub_skipped);
         }
 
-        Ok(files)
+        Ok((files, skipped))
     }
 
     /// Collect directory results for analysis with parallel processing
@@ -102,26 +517,63 @@ impl FileTraverser {
         path: &Path,
         max_depth: u32,
         analyze_file: F,
-    ) -> Result<Vec<(PathBuf, EntryType)>, String>
+    ) -> Result<(Vec<(PathBuf, EntryType)>, Vec<SkippedEntry>), String>
     where
         F: Fn(&Path) -> Result<AnalysisResult, String> + Sync,
     {
-        let files_to_analyze = self.collect_files_recursive(path, 0, max_depth)?;
-
+        let root_canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let (files_to_analyze, skipped) = self.collect_files_recursive(
+            path,
+            path,
+            0,
+            max_depth,
+            &[],
+            &root_canonical,
+            std::slice::from_ref(&root_canonical),
+        )?;
+
+        let reporter = ProgressReporter::new(self.progress.clone());
+        let total = files_to_analyze.len();
+        reporter.emit(ProgressData {
+            current_stage: 1,
+            max_stage: 2,
+            entries_to_check: total,
+            entries_checked: 0,
+        });
+
+        let entries_checked = AtomicUsize::new(0);
         let results: Result<Vec<_>, String> = files_to_analyze
             .par_iter()
             .map(|file_path| {
-                analyze_file(file_path).map(|result| (file_path.clone(), EntryType::File(result)))
+                if let Some(stop_flag) = &self.stop_flag
+                    && stop_flag.load(Ordering::Relaxed)
+                {
+                    return Err("Analysis stopped".to_string());
+                }
+
+                let result = analyze_file(file_path)
+                    .map(|result| (file_path.clone(), EntryType::File(result)));
+
+                let checked = entries_checked.fetch_add(1, Ordering::Relaxed) + 1;
+                reporter.emit_throttled(ProgressData {
+                    current_stage: 2,
+                    max_stage: 2,
+                    entries_to_check: total,
+                    entries_checked: checked,
+                });
+
+                result
             })
             .collect();
 
-        results
+        Ok((results?, skipped))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
 
     #[test]
     fn validate_existing_path() {
@@ -231,14 +683,213 @@ mod tests {
     fn collect_directory_results_works() {
         let t = FileTraverser::new();
         let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
-        let results = t
+        let (results, skipped) = t
             .collect_directory_results(&fixtures, 3, |_path| Ok(AnalysisResult::empty(1)))
             .unwrap();
         assert!(results.len() >= 4);
+        assert!(skipped.is_empty());
     }
 
     #[test]
     fn default_traverser() {
         let _t = FileTraverser::default();
     }
+
+    #[test]
+    fn gitignore_excludes_matching_files_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "*.log\nbuild\n").unwrap();
+        std::fs::write(dir.path().join("keep.rs"), "fn keep() {}").unwrap();
+        std::fs::write(dir.path().join("trace.log"), "not code").unwrap();
+        let build = dir.path().join("build");
+        std::fs::create_dir(&build).unwrap();
+        std::fs::write(build.join("artifact.rs"), "fn generated() {}").unwrap();
+
+        let t = FileTraverser::new();
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert!(names.contains(&"keep.rs".to_string()));
+        assert!(!names.contains(&"artifact.rs".to_string()));
+    }
+
+    #[test]
+    fn nested_gitignore_can_re_include_a_pattern_ignored_above() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "*.rs\n").unwrap();
+        std::fs::write(dir.path().join("top.rs"), "fn top() {}").unwrap();
+
+        let sub = dir.path().join("keep");
+        std::fs::create_dir(&sub).unwrap();
+        std::fs::write(sub.join(".gitignore"), "!*.rs\n").unwrap();
+        std::fs::write(sub.join("nested.rs"), "fn nested() {}").unwrap();
+
+        let t = FileTraverser::new();
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert!(!names.contains(&"top.rs".to_string()));
+        assert!(names.contains(&"nested.rs".to_string()));
+    }
+
+    #[test]
+    fn exclude_pattern_prunes_matching_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("keep.rs"), "fn keep() {}").unwrap();
+        let fixtures = dir.path().join("fixtures");
+        std::fs::create_dir(&fixtures).unwrap();
+        std::fs::write(fixtures.join("sample.rs"), "fn sample() {}").unwrap();
+
+        let t = FileTraverser::new().with_exclude(vec!["fixtures".to_string()]);
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert!(names.contains(&"keep.rs".to_string()));
+        assert!(!names.contains(&"sample.rs".to_string()));
+    }
+
+    #[test]
+    fn include_pattern_restricts_to_matching_base_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "fn lib() {}").unwrap();
+        let docs = dir.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        std::fs::write(docs.join("notes.rs"), "fn notes() {}").unwrap();
+
+        let t = FileTraverser::new().with_include(vec!["src/**/*.rs".to_string()]);
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert!(names.contains(&"lib.rs".to_string()));
+        assert!(!names.contains(&"notes.rs".to_string()));
+    }
+
+    #[test]
+    fn glob_pattern_matches_double_star_and_wildcards() {
+        let pattern = GlobPattern::new("src/**/*.rs");
+        assert!(pattern.matches("src/main.rs"));
+        assert!(pattern.matches("src/analyze/graph.rs"));
+        assert!(!pattern.matches("src/main.py"));
+        assert!(!pattern.matches("tests/main.rs"));
+    }
+
+    #[test]
+    fn glob_pattern_literal_prefix_stops_at_first_wildcard() {
+        let pattern = GlobPattern::new("src/analyze/**/*.rs");
+        assert_eq!(pattern.literal_prefix(), PathBuf::from("src/analyze"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn self_referential_symlink_is_reported_as_infinite_recursion() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("real.rs"), "fn real() {}").unwrap();
+        symlink(dir.path(), dir.path().join("loop")).unwrap();
+
+        let t = FileTraverser::new().with_follow_symlinks(true);
+        let (files, skipped) = t.collect_files_with_skipped(dir.path(), 0).unwrap();
+
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert_eq!(names.iter().filter(|n| *n == "real.rs").count(), 1);
+        assert!(
+            skipped
+                .iter()
+                .any(|s| s.error == TraversalError::InfiniteRecursion)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlink_is_reported_as_non_existent_file() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        symlink(dir.path().join("missing"), dir.path().join("broken")).unwrap();
+
+        let t = FileTraverser::new();
+        let (_, skipped) = t.collect_files_with_skipped(dir.path(), 0).unwrap();
+
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].error, TraversalError::NonExistentFile);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_escaping_root_is_skipped_by_default() {
+        use std::os::unix::fs::symlink;
+
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("external.rs"), "fn external() {}").unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        symlink(outside.path(), dir.path().join("elsewhere")).unwrap();
+
+        let t = FileTraverser::new();
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        assert!(files.is_empty());
+
+        let t = FileTraverser::new().with_follow_symlinks(true);
+        let files = t.collect_files_for_focused(dir.path(), 0).unwrap();
+        let names: Vec<String> = files
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        assert!(names.contains(&"external.rs".to_string()));
+    }
+
+    #[test]
+    fn progress_reports_enumeration_and_completion_stages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let recorded = stages.clone();
+        let t = FileTraverser::new().with_progress(Arc::new(move |data: ProgressData| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((data.current_stage, data.entries_to_check));
+        }));
+
+        let (results, _) = t
+            .collect_directory_results(dir.path(), 0, |_path| Ok(AnalysisResult::empty(1)))
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let stages = stages.lock().unwrap();
+        assert!(
+            stages
+                .iter()
+                .any(|(stage, total)| *stage == 1 && *total == 2)
+        );
+    }
+
+    #[test]
+    fn stop_flag_aborts_parallel_analysis() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+
+        let stop_flag = Arc::new(AtomicBool::new(true));
+        let t = FileTraverser::new().with_stop_flag(stop_flag);
+
+        let result =
+            t.collect_directory_results(dir.path(), 0, |_path| Ok(AnalysisResult::empty(1)));
+        assert!(result.is_err());
+    }
 }
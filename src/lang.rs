@@ -3,48 +3,355 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::path::Path;
+use std::sync::{Mutex, OnceLock};
 
-/// Get the markdown language identifier for a file extension
-pub fn get_language_identifier(path: &Path) -> &'static str {
-    match path.extension().and_then(|ext| ext.to_str()) {
-        Some("rs") => "rust",
-        Some("hs") => "haskell",
-        Some("rkt") | Some("scm") => "scheme",
-        Some("py") => "python",
-        Some("js") => "javascript",
-        Some("ts") => "typescript",
-        Some("json") => "json",
-        Some("toml") => "toml",
-        Some("yaml") | Some("yml") => "yaml",
-        Some("sh") => "bash",
-        Some("ps1") => "powershell",
-        Some("bat") | Some("cmd") => "batch",
-        Some("vbs") => "vbscript",
-        Some("go") => "go",
-        Some("md") => "markdown",
-        Some("html") => "html",
-        Some("css") => "css",
-        Some("sql") => "sql",
-        Some("java") => "java",
-        Some("cpp") | Some("cc") | Some("cxx") => "cpp",
-        Some("c") => "c",
-        Some("h") | Some("hpp") => "cpp",
-        Some("rb") => "ruby",
-        Some("php") => "php",
-        Some("swift") => "swift",
-        Some("kt") | Some("kts") => "kotlin",
-        Some("scala") => "scala",
-        Some("r") => "r",
-        Some("m") => "matlab",
-        Some("pl") => "perl",
-        Some("dockerfile") => "dockerfile",
-        _ => "",
+use crate::analyze::lock_or_recover;
+
+/// One recognizable language: the exact filenames, extensions, shebang interpreter names, and
+/// content substrings that identify it, checked in that order (most to least specific). An
+/// extension or signature may appear on more than one matcher (e.g. `.m` on both `matlab` and
+/// `objectivec`); ties are broken by content signature, then by registration order.
+#[derive(Debug, Clone, Copy)]
+pub struct LanguageMatcher {
+    pub language: &'static str,
+    pub filenames: &'static [&'static str],
+    pub extensions: &'static [&'static str],
+    pub shebang_interpreters: &'static [&'static str],
+    pub content_signatures: &'static [&'static str],
+}
+
+fn builtin_matchers() -> Vec<LanguageMatcher> {
+    vec![
+        LanguageMatcher {
+            language: "makefile",
+            filenames: &["Makefile", "makefile", "GNUmakefile"],
+            extensions: &[],
+            shebang_interpreters: &["make"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "dockerfile",
+            filenames: &["Dockerfile", "dockerfile"],
+            extensions: &["dockerfile"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "ruby",
+            filenames: &["Gemfile", "Rakefile", "gemfile", "rakefile"],
+            extensions: &["rb"],
+            shebang_interpreters: &["ruby"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "rust",
+            filenames: &[],
+            extensions: &["rs"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "haskell",
+            filenames: &[],
+            extensions: &["hs"],
+            shebang_interpreters: &["runghc", "runhaskell"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "scheme",
+            filenames: &[],
+            extensions: &["rkt", "scm"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "python",
+            filenames: &[],
+            extensions: &["py"],
+            shebang_interpreters: &["python", "python2", "python3"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "javascript",
+            filenames: &[],
+            extensions: &["js"],
+            shebang_interpreters: &["node", "nodejs"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "typescript",
+            filenames: &[],
+            extensions: &["ts"],
+            shebang_interpreters: &["ts-node"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "json",
+            filenames: &[],
+            extensions: &["json"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "toml",
+            filenames: &[],
+            extensions: &["toml"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "yaml",
+            filenames: &[],
+            extensions: &["yaml", "yml"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "bash",
+            filenames: &[],
+            extensions: &["sh"],
+            shebang_interpreters: &["bash", "sh", "zsh", "dash"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "powershell",
+            filenames: &[],
+            extensions: &["ps1"],
+            shebang_interpreters: &["pwsh"],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "batch",
+            filenames: &[],
+            extensions: &["bat", "cmd"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "vbscript",
+            filenames: &[],
+            extensions: &["vbs"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "go",
+            filenames: &[],
+            extensions: &["go"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "markdown",
+            filenames: &[],
+            extensions: &["md"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "html",
+            filenames: &[],
+            extensions: &["html"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "css",
+            filenames: &[],
+            extensions: &["css"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "sql",
+            filenames: &[],
+            extensions: &["sql"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "java",
+            filenames: &[],
+            extensions: &["java"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "cpp",
+            filenames: &[],
+            extensions: &["cpp", "cc", "cxx", "h", "hpp"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "c",
+            filenames: &[],
+            extensions: &["c"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "php",
+            filenames: &[],
+            extensions: &["php"],
+            shebang_interpreters: &["php"],
+            content_signatures: &["<?php"],
+        },
+        LanguageMatcher {
+            language: "swift",
+            filenames: &[],
+            extensions: &["swift"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "kotlin",
+            filenames: &[],
+            extensions: &["kt", "kts"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "scala",
+            filenames: &[],
+            extensions: &["scala"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "r",
+            filenames: &[],
+            extensions: &["r"],
+            shebang_interpreters: &["Rscript"],
+            content_signatures: &[],
+        },
+        // `.m` is ambiguous: Matlab is the default, but files containing Objective-C's
+        // characteristic directives/keywords are recognized as `objectivec` instead.
+        LanguageMatcher {
+            language: "matlab",
+            filenames: &[],
+            extensions: &["m"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        },
+        LanguageMatcher {
+            language: "objectivec",
+            filenames: &[],
+            extensions: &["m"],
+            shebang_interpreters: &[],
+            content_signatures: &["#import <Foundation", "@interface", "@implementation"],
+        },
+        LanguageMatcher {
+            language: "perl",
+            filenames: &[],
+            extensions: &["pl"],
+            shebang_interpreters: &["perl"],
+            content_signatures: &[],
+        },
+    ]
+}
+
+fn registry() -> &'static Mutex<Vec<LanguageMatcher>> {
+    static REGISTRY: OnceLock<Mutex<Vec<LanguageMatcher>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(builtin_matchers()))
+}
+
+/// Register an additional language matcher at runtime, so downstream users can teach the
+/// analyzer about a grammar beyond the built-in set without editing this crate. Matchers are
+/// consulted in registration order, so this entry is tried after every matcher already
+/// registered (builtins first).
+pub fn register_language(matcher: LanguageMatcher) {
+    let mut matchers = lock_or_recover(registry(), |m| *m = builtin_matchers());
+    matchers.push(matcher);
+}
+
+/// Parse a shebang line's interpreter name, unwrapping the `env`-invocation form
+/// (`#!/usr/bin/env python3` -> `python3`) as well as a direct path (`#!/bin/bash` -> `bash`).
+fn shebang_interpreter(first_line: &str) -> Option<String> {
+    let rest = first_line.strip_prefix("#!")?.trim();
+    let mut tokens = rest.split_whitespace();
+    let program = Path::new(tokens.next()?).file_name()?.to_str()?;
+
+    if program == "env" {
+        Some(tokens.next()?.to_string())
+    } else {
+        Some(program.to_string())
+    }
+}
+
+fn identify(matchers: &[LanguageMatcher], path: &Path) -> &'static str {
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if let Some(matcher) = matchers.iter().find(|m| m.filenames.contains(&filename)) {
+        return matcher.language;
     }
+
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let candidates: Vec<&LanguageMatcher> = if extension.is_empty() {
+        vec![]
+    } else {
+        matchers
+            .iter()
+            .filter(|m| m.extensions.contains(&extension))
+            .collect()
+    };
+
+    // Only read the file when a tie needs breaking or nothing matched by name, so the common
+    // single-extension-match case stays a pure path lookup.
+    let content = if candidates.len() != 1 && path.is_file() {
+        std::fs::read_to_string(path).ok()
+    } else {
+        None
+    };
+
+    if !candidates.is_empty() {
+        if let Some(content) = &content
+            && let Some(matcher) = candidates
+                .iter()
+                .find(|m| m.content_signatures.iter().any(|sig| content.contains(sig)))
+        {
+            return matcher.language;
+        }
+        return candidates[0].language;
+    }
+
+    let Some(content) = content else {
+        return "";
+    };
+
+    if let Some(interpreter) = content.lines().next().and_then(shebang_interpreter)
+        && let Some(matcher) = matchers
+            .iter()
+            .find(|m| m.shebang_interpreters.contains(&interpreter.as_str()))
+    {
+        return matcher.language;
+    }
+
+    matchers
+        .iter()
+        .find(|m| m.content_signatures.iter().any(|sig| content.contains(sig)))
+        .map(|m| m.language)
+        .unwrap_or("")
+}
+
+/// Identify a file's language by exact filename, then extension, then (for files that exist on
+/// disk and didn't match either) a shebang interpreter or content signature.
+pub fn get_language_identifier(path: &Path) -> &'static str {
+    let matchers = lock_or_recover(registry(), |m| *m = builtin_matchers());
+    identify(&matchers, path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
+
+    fn write_fixture(name: &str, content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        (dir, path)
+    }
 
     #[test]
     fn detect_rust() {
@@ -119,7 +426,70 @@ mod tests {
     }
 
     #[test]
-    fn no_extension_returns_empty() {
-        assert_eq!(get_language_identifier(Path::new("Makefile")), "");
+    fn detect_makefile_by_filename() {
+        assert_eq!(get_language_identifier(Path::new("Makefile")), "makefile");
+        assert_eq!(
+            get_language_identifier(Path::new("GNUmakefile")),
+            "makefile"
+        );
+    }
+
+    #[test]
+    fn detect_dockerfile_by_filename() {
+        assert_eq!(
+            get_language_identifier(Path::new("Dockerfile")),
+            "dockerfile"
+        );
+    }
+
+    #[test]
+    fn detect_gemfile_and_rakefile_as_ruby() {
+        assert_eq!(get_language_identifier(Path::new("Gemfile")), "ruby");
+        assert_eq!(get_language_identifier(Path::new("Rakefile")), "ruby");
+    }
+
+    #[test]
+    fn detect_python_shebang_script_without_extension() {
+        let (_dir, path) = write_fixture("run", "#!/usr/bin/env python3\nprint('hi')\n");
+        assert_eq!(get_language_identifier(&path), "python");
+    }
+
+    #[test]
+    fn detect_bash_shebang_script_without_env() {
+        let (_dir, path) = write_fixture("run", "#!/bin/bash\necho hi\n");
+        assert_eq!(get_language_identifier(&path), "bash");
+    }
+
+    #[test]
+    fn extensionless_file_without_shebang_returns_empty() {
+        let (_dir, path) = write_fixture("README", "just some text\n");
+        assert_eq!(get_language_identifier(&path), "");
+    }
+
+    #[test]
+    fn ambiguous_m_extension_defaults_to_matlab() {
+        let (_dir, path) = write_fixture("script.m", "function y = f(x)\n  y = x + 1;\nend\n");
+        assert_eq!(get_language_identifier(&path), "matlab");
+    }
+
+    #[test]
+    fn ambiguous_m_extension_detects_objectivec_by_content() {
+        let (_dir, path) = write_fixture(
+            "AppDelegate.m",
+            "#import <Foundation/Foundation.h>\n@implementation AppDelegate\n@end\n",
+        );
+        assert_eq!(get_language_identifier(&path), "objectivec");
+    }
+
+    #[test]
+    fn register_language_extends_recognized_extensions() {
+        register_language(LanguageMatcher {
+            language: "zig",
+            filenames: &[],
+            extensions: &["zig"],
+            shebang_interpreters: &[],
+            content_signatures: &[],
+        });
+        assert_eq!(get_language_identifier(Path::new("main.zig")), "zig");
     }
 }
@@ -2,7 +2,16 @@
 // Copyright 2025 utapyngo (modifications)
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+
+/// Output rendering for the analysis result
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-formatted text (default)
+    Text,
+    /// Machine-readable JSON
+    Json,
+}
 
 /// Analyze code structure and relationships using tree-sitter parsing.
 ///
@@ -29,16 +38,54 @@ struct Args {
     focus: Option<String>,
 
     /// Call graph depth. 0=where defined, 1=direct callers/callees, 2+=transitive chains
-    #[arg(short = 'd', long, default_value_t = 2)]
-    follow_depth: u32,
+    /// (default: 2, or a project `.code-analyzer.toml`'s `follow_depth`)
+    #[arg(short = 'd', long)]
+    follow_depth: Option<u32>,
 
-    /// Directory recursion limit. 0=unlimited
-    #[arg(short = 'm', long, default_value_t = 3)]
-    max_depth: u32,
+    /// Directory recursion limit. 0=unlimited (default: 3, or a project `.code-analyzer.toml`'s
+    /// `max_depth`)
+    #[arg(short = 'm', long)]
+    max_depth: Option<u32>,
 
     /// Maximum depth for recursive AST traversal (prevents stack overflow in deeply nested code)
     #[arg(long)]
     ast_recursion_limit: Option<usize>,
+
+    /// Disable the on-disk analysis cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Directory for the on-disk analysis cache (default: .code-analyzer-cache next to path)
+    #[arg(long)]
+    cache_dir: Option<String>,
+
+    /// Use the mtime-keyed on-disk cache instead of the default content-hashed one (or a
+    /// project `.code-analyzer.toml`'s `mtime_cache`)
+    #[arg(long)]
+    mtime_cache: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    output: OutputFormat,
+
+    /// List functions/classes/methods with no incoming callers or type references
+    /// (excluding `main` and test functions) instead of running the normal analysis
+    #[arg(long)]
+    unreferenced: bool,
+
+    /// Also render each focused-symbol definition as a labeled source excerpt with this many
+    /// lines of context above/below, after the usual file:line references (requires --focus)
+    #[arg(long)]
+    context_lines: Option<usize>,
+
+    /// Restrict traversal to files matching this glob pattern (e.g. `src/**/*.rs`). May be
+    /// repeated; `.gitignore`/`.ignore` files are always honored
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Prune files/directories matching this glob pattern (e.g. `**/*_test.go`). May be repeated
+    #[arg(long)]
+    exclude: Vec<String>,
 }
 
 fn main() {
@@ -63,14 +110,66 @@ fn main() {
         .to_string_lossy()
         .to_string();
 
-    let result = code_analyze::analyze(
-        &args.path,
-        args.focus.as_deref(),
-        args.follow_depth,
-        args.max_depth,
-        args.ast_recursion_limit,
-        &cwd,
-    );
+    // Every branch below honors a discovered `.code-analyzer.toml` for any of
+    // follow-depth/max-depth/ast-recursion-limit/include/exclude/mtime-cache the user didn't
+    // pass explicitly, and the same `--cache-dir`/`--no-cache`/`--mtime-cache`/`--include`/
+    // `--exclude` flags, not just the default branch.
+    let result = if args.unreferenced {
+        code_analyze::analyze_unreferenced(
+            &args.path,
+            args.max_depth,
+            args.ast_recursion_limit,
+            &cwd,
+            args.cache_dir.as_deref(),
+            args.no_cache,
+            args.mtime_cache,
+            args.include,
+            args.exclude,
+        )
+    } else if let (Some(focus), Some(context_lines)) = (&args.focus, args.context_lines) {
+        code_analyze::analyze_focused_snippets(
+            &args.path,
+            focus,
+            args.follow_depth,
+            args.max_depth,
+            context_lines,
+            args.ast_recursion_limit,
+            &cwd,
+            args.cache_dir.as_deref(),
+            args.no_cache,
+            args.mtime_cache,
+            args.include,
+            args.exclude,
+        )
+    } else if args.output == OutputFormat::Json {
+        code_analyze::analyze_json(
+            &args.path,
+            args.focus.as_deref(),
+            args.follow_depth,
+            args.max_depth,
+            args.ast_recursion_limit,
+            &cwd,
+            args.cache_dir.as_deref(),
+            args.no_cache,
+            args.mtime_cache,
+            args.include,
+            args.exclude,
+        )
+    } else {
+        code_analyze::analyze_configured(
+            &args.path,
+            args.focus.as_deref(),
+            args.follow_depth,
+            args.max_depth,
+            args.ast_recursion_limit,
+            &cwd,
+            args.cache_dir.as_deref(),
+            args.no_cache,
+            args.mtime_cache,
+            args.include,
+            args.exclude,
+        )
+    };
 
     print!("{}", result);
 }
@@ -258,3 +258,216 @@ fn analyze_relative_path() {
     );
     assert!(out.contains("main"), "expected 'main' function:\n{out}");
 }
+
+// ── Config-file driven defaults (analyze_configured) ───────────────────
+
+#[test]
+fn analyze_configured_without_config_file_uses_builtin_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn main() {}\n").unwrap();
+
+    let out = code_analyze::analyze_configured(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        None,
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("SUMMARY:"), "expected SUMMARY:\n{out}");
+}
+
+#[test]
+fn analyze_configured_honors_discovered_config_excludes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("keep.rs"), "fn kept() {}\n").unwrap();
+    std::fs::write(dir.path().join("skip_test.rs"), "fn skipped() {}\n").unwrap();
+    std::fs::write(
+        dir.path().join(".code-analyzer.toml"),
+        "[files]\nexclude = [\"**/*_test.rs\"]\n",
+    )
+    .unwrap();
+
+    let out = code_analyze::analyze_configured(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        None,
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("keep.rs"), "expected keep.rs:\n{out}");
+    assert!(!out.contains("skip_test.rs"), "expected skip_test.rs pruned:\n{out}");
+}
+
+#[test]
+fn analyze_configured_uses_mtime_cache_when_requested() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn main() {}\n").unwrap();
+    let cache_dir = dir.path().join(".code-analyzer-cache");
+
+    let out = code_analyze::analyze_configured(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        None,
+        None,
+        &cwd(),
+        Some(&cache_dir.to_string_lossy()),
+        false,
+        true,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("SUMMARY:"), "expected SUMMARY:\n{out}");
+    assert!(
+        cache_dir.join("mtime-cache.bin").is_file(),
+        "expected the mtime cache file to be written under {}",
+        cache_dir.display()
+    );
+}
+
+#[test]
+fn analyze_configured_honors_mtime_cache_from_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.path().join(".code-analyzer.toml"), "mtime_cache = true\n").unwrap();
+    let cache_dir = dir.path().join(".code-analyzer-cache");
+
+    let out = code_analyze::analyze_configured(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        None,
+        None,
+        &cwd(),
+        Some(&cache_dir.to_string_lossy()),
+        false,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("SUMMARY:"), "expected SUMMARY:\n{out}");
+    assert!(
+        cache_dir.join("mtime-cache.bin").is_file(),
+        "expected the config file's mtime_cache = true to select the mtime cache backend"
+    );
+}
+
+#[test]
+fn analyze_configured_explicit_args_override_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.path().join(".code-analyzer.toml"), "max_depth = 0\n").unwrap();
+
+    // An explicit max_depth of 1 should win over the config file's 0.
+    let out = code_analyze::analyze_configured(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        Some(1),
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("SUMMARY:"), "expected SUMMARY:\n{out}");
+}
+
+// ── Config/include/exclude plumbing on every CLI entry point ───────────
+
+#[test]
+fn analyze_unreferenced_honors_discovered_config_excludes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("keep.rs"), "fn kept() {}\n").unwrap();
+    std::fs::write(dir.path().join("skip_test.rs"), "fn skipped() {}\n").unwrap();
+    std::fs::write(
+        dir.path().join(".code-analyzer.toml"),
+        "[files]\nexclude = [\"**/*_test.rs\"]\n",
+    )
+    .unwrap();
+
+    let out = code_analyze::analyze_unreferenced(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("kept"), "expected kept:\n{out}");
+    assert!(!out.contains("skipped"), "expected skipped pruned:\n{out}");
+}
+
+#[test]
+fn analyze_json_honors_discovered_config_excludes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("keep.rs"), "fn kept() {}\n").unwrap();
+    std::fs::write(dir.path().join("skip_test.rs"), "fn skipped() {}\n").unwrap();
+    std::fs::write(
+        dir.path().join(".code-analyzer.toml"),
+        "[files]\nexclude = [\"**/*_test.rs\"]\n",
+    )
+    .unwrap();
+
+    let out = code_analyze::analyze_json(
+        &dir.path().to_string_lossy(),
+        None,
+        None,
+        None,
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("keep.rs"), "expected keep.rs:\n{out}");
+    assert!(!out.contains("skip_test.rs"), "expected skip_test.rs pruned:\n{out}");
+}
+
+#[test]
+fn analyze_focused_snippets_honors_discovered_config_excludes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("keep.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.path().join("skip_test.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(
+        dir.path().join(".code-analyzer.toml"),
+        "[files]\nexclude = [\"**/*_test.rs\"]\n",
+    )
+    .unwrap();
+
+    let out = code_analyze::analyze_focused_snippets(
+        &dir.path().to_string_lossy(),
+        "main",
+        None,
+        None,
+        2,
+        None,
+        &cwd(),
+        None,
+        true,
+        false,
+        vec![],
+        vec![],
+    );
+    assert!(out.contains("keep.rs"), "expected keep.rs:\n{out}");
+    assert!(!out.contains("skip_test.rs"), "expected skip_test.rs pruned:\n{out}");
+}